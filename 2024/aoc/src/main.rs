@@ -0,0 +1,172 @@
+use std::env;
+use std::process::ExitCode;
+
+use common::{input, Part};
+
+mod registry;
+
+use registry::Day;
+
+const USAGE: &str = "Usage: aoc run <day|all> [--part <1|2>] [--input <PATH|->] [--time]
+       aoc list
+
+Without --input, inputs are read from $AOC_INPUT_DIR/dayXX.txt, or from the
+`inputs` folder of the workspace.";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run { days: Vec<u8>, part: Option<Part>, input: Option<String>, time: bool },
+    List,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match parse_args(&args) {
+        Ok(Command::List) => {
+            for day in &registry::DAYS {
+                println!("Day {:02}", day.number);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Run { days, part, input, time }) => {
+            for number in days {
+                let day = registry::find(number).expect("days are validated by parse_args");
+                let source = input::resolve(day.number, input.as_deref());
+
+                let result = input::read(&source)
+                    .map_err(|error| error.to_string())
+                    .and_then(|input| {
+                        run_day(day, part, &input, time).map_err(|error| error.with_file(source.to_string()).to_string())
+                    });
+
+                if let Err(error) = result {
+                    eprintln!("Day {:02}: {}", day.number, error);
+                    return ExitCode::FAILURE;
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run_day(day: &Day, part: Option<Part>, input: &str, time: bool) -> common::Result<()> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let report = (day.solve)(input, &parts)?;
+
+    if time {
+        println!("Day {:02} - Parse ({:?})", day.number, report.parse_time);
+    }
+
+    for answer in report.answers {
+        if time {
+            println!("Day {:02} - Part {}: {} ({:?})", day.number, answer.part, answer.value, answer.elapsed);
+        } else {
+            println!("Day {:02} - Part {}: {}", day.number, answer.part, answer.value);
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+
+    match args.next().map(String::as_str) {
+        Some("list") => Ok(Command::List),
+        Some("run") => {
+            let days = match args.next().map(String::as_str) {
+                Some("all") => registry::DAYS.iter().map(|day| day.number).collect(),
+                Some(day) => {
+                    let number: u8 = day.parse().map_err(|_| format!("Invalid day `{}`", day))?;
+                    if registry::find(number).is_none() {
+                        return Err(format!("Day {} is not solved yet", number));
+                    }
+                    vec![number]
+                }
+                None => return Err("Missing day".to_string()),
+            };
+
+            let mut part = None;
+            let mut input = None;
+            let mut time = false;
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--part" | "-p" => {
+                        let value = args.next().ok_or("Missing value for --part")?;
+                        match value.as_str() {
+                            "1" => part = Some(Part::One),
+                            "2" => part = Some(Part::Two),
+                            other => return Err(format!("Invalid part `{}`, expected 1 or 2", other)),
+                        }
+                    }
+                    "--input" | "-i" => {
+                        let value = args.next().ok_or("Missing value for --input")?;
+                        input = Some(value.clone());
+                    }
+                    "--time" | "-t" => time = true,
+                    other => return Err(format!("Unknown argument `{}`", other)),
+                }
+            }
+
+            if input.is_some() && days.len() > 1 {
+                return Err("--input can only be used when running a single day".to_string());
+            }
+
+            Ok(Command::Run { days, part, input, time })
+        }
+        Some(other) => Err(format!("Unknown command `{}`", other)),
+        None => Err("Missing command".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_args_test() {
+        assert_eq!(
+            parse_args(&args("run 5 --part 2")),
+            Ok(Command::Run { days: vec![5], part: Some(Part::Two), input: None, time: false })
+        );
+        assert_eq!(
+            parse_args(&args("run 3 --time")),
+            Ok(Command::Run { days: vec![3], part: None, input: None, time: true })
+        );
+        assert_eq!(
+            parse_args(&args("run all")),
+            Ok(Command::Run { days: vec![1, 2, 3, 4, 5, 6], part: None, input: None, time: false })
+        );
+        assert_eq!(
+            parse_args(&args("run 1 --input - --part 1")),
+            Ok(Command::Run { days: vec![1], part: Some(Part::One), input: Some("-".to_string()), time: false })
+        );
+        assert_eq!(parse_args(&args("list")), Ok(Command::List));
+    }
+
+    #[test]
+    fn parse_args_errors() {
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("run")).is_err());
+        assert!(parse_args(&args("run 42")).is_err());
+        assert!(parse_args(&args("run five")).is_err());
+        assert!(parse_args(&args("run 1 --part 3")).is_err());
+        assert!(parse_args(&args("run 1 --verbose")).is_err());
+        assert!(parse_args(&args("run 1 --input")).is_err());
+        assert!(parse_args(&args("run all --input day01.txt")).is_err());
+    }
+}
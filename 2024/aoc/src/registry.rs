@@ -0,0 +1,48 @@
+use common::solution::Report;
+use common::{Part, Solution};
+
+/// Parse an input and run the requested parts on it.
+pub type Solver = fn(&str, &[Part]) -> common::Result<Report>;
+
+pub struct Day {
+    pub number: u8,
+    pub solve: Solver,
+}
+
+impl Day {
+    const fn new<S: Solution>() -> Day {
+        Day { number: S::DAY, solve: common::solve::<S> }
+    }
+}
+
+pub const DAYS: [Day; 6] = [
+    Day::new::<day01::Day01>(),
+    Day::new::<day02::Day02>(),
+    Day::new::<day03::Day03>(),
+    Day::new::<day04::Day04>(),
+    Day::new::<day05::Day05>(),
+    Day::new::<day06::Day06>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_sorted_and_unique() {
+        for (index, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.number as usize, index + 1);
+        }
+    }
+
+    #[test]
+    fn find_test() {
+        assert_eq!(find(5).map(|day| day.number), Some(5));
+        assert!(find(0).is_none());
+        assert!(find(26).is_none());
+    }
+}
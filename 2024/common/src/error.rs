@@ -0,0 +1,157 @@
+use std::fmt;
+use std::str::FromStr;
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// An invalid input, pointing at the offending text like a compiler would:
+///
+/// ```text
+/// error: invalid number `12a`: invalid digit found in string
+///  --> inputs/day01.txt:3:6
+///   |
+/// 3 | 3245 12a
+///   |      ^^^
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub file: Option<String>,
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number in characters, starting at 1.
+    pub column: usize,
+    /// The whole offending line.
+    pub text: String,
+    /// Width of the offending text, in characters.
+    pub width: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Build an error on `span`, which must be a slice of `line`. The span
+    /// is placed at the end of the line when it is not.
+    pub fn at(line_number: usize, line: &str, span: &str, message: impl Into<String>) -> ParseError {
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| offset + span.len() <= line.len() && line.is_char_boundary(offset))
+            .unwrap_or(line.len());
+
+        ParseError {
+            file: None,
+            line: line_number,
+            column: line[..offset].chars().count() + 1,
+            text: line.to_string(),
+            width: span.chars().count(),
+            message: message.into(),
+        }
+    }
+
+    /// Build an error just after the last character of `line`, for something
+    /// missing.
+    pub fn end_of_line(line_number: usize, line: &str, message: impl Into<String>) -> ParseError {
+        ParseError::at(line_number, line, &line[line.len()..], message)
+    }
+
+    /// Build an error on the whole `line`.
+    pub fn line(line_number: usize, line: &str, message: impl Into<String>) -> ParseError {
+        ParseError::at(line_number, line, line, message)
+    }
+
+    pub fn with_file(mut self, file: impl Into<String>) -> ParseError {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+        let file = self.file.as_deref().unwrap_or("<input>");
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", margin, file, self.line, self.column)?;
+        writeln!(f, "{} |", margin)?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(f, "{} | {}{}", margin, " ".repeat(self.column - 1), "^".repeat(self.width.max(1)))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `token`, a slice of `line`, reporting it when it is invalid.
+pub fn parse_token<T>(line_number: usize, line: &str, token: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    token
+        .parse()
+        .map_err(|error| ParseError::at(line_number, line, token, format!("invalid number `{}`: {}", token, error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_test() {
+        let line = "3245 12a";
+        let error = ParseError::at(3, line, &line[5..], "invalid number");
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 6);
+        assert_eq!(error.width, 3);
+        assert_eq!(error.text, line);
+    }
+
+    #[test]
+    fn at_outside_of_line() {
+        let error = ParseError::at(1, "1234", "", "expected two numbers");
+        assert_eq!(error.column, 5);
+        assert_eq!(error.width, 0);
+
+        // A span inside the line, but not on a character boundary.
+        let line = "é1";
+        let bytes = line.as_bytes();
+        let span = std::str::from_utf8(&bytes[1..1]).unwrap();
+        let error = ParseError::at(1, line, span, "nowhere");
+        assert_eq!(error.column, 3);
+    }
+
+    #[test]
+    fn end_of_line_test() {
+        let error = ParseError::end_of_line(2, "12 é", "expected two numbers");
+        assert_eq!((error.line, error.column, error.width), (2, 5, 0));
+        assert_eq!(error.text, "12 é");
+
+        assert_eq!(ParseError::end_of_line(1, "", "empty").column, 1);
+    }
+
+    #[test]
+    fn display_test() {
+        let line = "3245 12a";
+        let error = parse_token::<u32>(3, line, &line[5..]).unwrap_err().with_file("day01.txt");
+
+        assert_eq!(
+            error.to_string(),
+            "error: invalid number `12a`: invalid digit found in string\n \
+             --> day01.txt:3:6\n  \
+             |\n\
+             3 | 3245 12a\n  \
+             |      ^^^"
+        );
+    }
+
+    #[test]
+    fn display_empty_span() {
+        let error = ParseError::line(12, "", "empty line");
+
+        assert_eq!(error.to_string(), "error: empty line\n  --> <input>:12:1\n   |\n12 | \n   | ^");
+    }
+
+    #[test]
+    fn columns_are_counted_in_chars() {
+        let line = "é,x";
+        let error = ParseError::at(1, line, &line[3..], "unexpected");
+        assert_eq!(error.column, 3);
+    }
+}
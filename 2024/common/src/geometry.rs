@@ -0,0 +1,253 @@
+//! Positions on a map and the directions to move between them, `y` growing
+//! downwards like the lines of the input.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Hash, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Vec2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Vec2) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The neighbour in `direction`.
+    pub fn next(self, direction: impl Into<Compass>) -> Vec2 {
+        self + direction.into().delta()
+    }
+
+    /// `None` when a coordinate overflows.
+    pub fn checked_add(self, other: Vec2) -> Option<Vec2> {
+        Some(Vec2 { x: self.x.checked_add(other.x)?, y: self.y.checked_add(other.y)? })
+    }
+
+    /// The neighbour in `direction`, `None` past the edge of the `i32` range.
+    pub fn checked_next(self, direction: impl Into<Compass>) -> Option<Vec2> {
+        self.checked_add(direction.into().delta())
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2 { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2 { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl Mul<i32> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, factor: i32) -> Vec2 {
+        Vec2 { x: self.x * factor, y: self.y * factor }
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2 { x: -self.x, y: -self.y }
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+/// One of the four directions along the axes.
+#[derive(Hash, Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Offset of one step in this direction.
+    pub fn delta(self) -> Vec2 {
+        Compass::from(self).delta()
+    }
+}
+
+/// One of the eight directions, diagonals included.
+#[derive(Hash, Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Compass {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Compass {
+    /// Every direction, clockwise from `Up`.
+    pub const ALL: [Compass; 8] = [
+        Compass::Up,
+        Compass::UpRight,
+        Compass::Right,
+        Compass::DownRight,
+        Compass::Down,
+        Compass::DownLeft,
+        Compass::Left,
+        Compass::UpLeft,
+    ];
+
+    /// Rotate clockwise by `eighths` of a full turn, counterclockwise when
+    /// negative.
+    pub fn rotate(self, eighths: i32) -> Compass {
+        Compass::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Compass {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Compass {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Compass {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Offset of one step in this direction.
+    pub fn delta(self) -> Vec2 {
+        match self {
+            Compass::Up => Vec2::new(0, -1),
+            Compass::UpRight => Vec2::new(1, -1),
+            Compass::Right => Vec2::new(1, 0),
+            Compass::DownRight => Vec2::new(1, 1),
+            Compass::Down => Vec2::new(0, 1),
+            Compass::DownLeft => Vec2::new(-1, 1),
+            Compass::Left => Vec2::new(-1, 0),
+            Compass::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(direction: Direction) -> Compass {
+        Compass::ALL[direction as usize * 2]
+    }
+}
+
+/// Only the directions along the axes convert, a diagonal is given back.
+impl TryFrom<Compass> for Direction {
+    type Error = Compass;
+
+    fn try_from(compass: Compass) -> Result<Direction, Compass> {
+        if compass.is_diagonal() {
+            return Err(compass);
+        }
+
+        Ok(Direction::ALL[compass as usize / 2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let a = Vec2::new(3, -2);
+        let b = Vec2::new(-1, 4);
+
+        assert_eq!(a + b, Vec2::new(2, 2));
+        assert_eq!(a - b, Vec2::new(4, -6));
+        assert_eq!(a * 3, Vec2::new(9, -6));
+        assert_eq!(-a, Vec2::new(-3, 2));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.manhattan(a), 0);
+
+        assert_eq!(a.checked_add(b), Some(Vec2::new(2, 2)));
+        assert_eq!(Vec2::new(i32::MAX, 0).checked_add(Vec2::new(1, 0)), None);
+        assert_eq!(Vec2::new(0, i32::MIN).checked_next(Direction::Up), None);
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Right.opposite(), Direction::Left);
+        assert_eq!(Direction::Down.delta(), Vec2::new(0, 1));
+        assert_eq!(Vec2::ZERO.next(Direction::Left), Vec2::new(-1, 0));
+    }
+
+    #[test]
+    fn compass() {
+        assert_eq!(Compass::Up.turn_right(), Compass::Right);
+        assert_eq!(Compass::Up.turn_left(), Compass::Left);
+        assert_eq!(Compass::Left.rotate(-1), Compass::DownLeft);
+        assert_eq!(Compass::UpRight.opposite(), Compass::DownLeft);
+        assert!(Compass::UpLeft.is_diagonal());
+        assert_eq!(Vec2::new(1, 1).next(Compass::DownLeft), Vec2::new(0, 2));
+    }
+
+    #[test]
+    fn conversions() {
+        for direction in Direction::ALL {
+            let compass = Compass::from(direction);
+
+            assert_eq!(compass.delta(), direction.delta());
+            assert_eq!(Compass::from(direction.turn_right()), compass.turn_right());
+            assert_eq!(Direction::try_from(compass), Ok(direction));
+        }
+
+        assert_eq!(Direction::try_from(Compass::DownRight), Err(Compass::DownRight));
+    }
+}
@@ -0,0 +1,105 @@
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Environment variable pointing to a folder containing `dayXX.txt` files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs");
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub source: InputSource,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot read input from {}: {}", self.source, self.error)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Pick where the input of `day` comes from.
+///
+/// In order: the `arg` path (`-` meaning stdin), the `AOC_INPUT_DIR` folder,
+/// then the `inputs` folder of the workspace.
+pub fn resolve(day: u8, arg: Option<&str>) -> InputSource {
+    resolve_with(day, arg, env::var(INPUT_DIR_VAR).ok().as_deref())
+}
+
+fn resolve_with(day: u8, arg: Option<&str>, input_dir: Option<&str>) -> InputSource {
+    match arg {
+        Some("-") => InputSource::Stdin,
+        Some(path) => InputSource::File(PathBuf::from(path)),
+        None => {
+            let dir = input_dir.unwrap_or(DEFAULT_INPUT_DIR);
+            InputSource::File(PathBuf::from(dir).join(format!("day{:02}.txt", day)))
+        }
+    }
+}
+
+pub fn read(source: &InputSource) -> Result<String, InputError> {
+    let result = match source {
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map(|_| input)
+        }
+        InputSource::File(path) => read_to_string(path),
+    };
+
+    result.map_err(|error| InputError { source: source.clone(), error })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_test() {
+        assert_eq!(resolve_with(3, Some("-"), Some("/tmp")), InputSource::Stdin);
+        assert_eq!(
+            resolve_with(3, Some("other.txt"), Some("/tmp")),
+            InputSource::File(PathBuf::from("other.txt"))
+        );
+        assert_eq!(
+            resolve_with(3, None, Some("/tmp")),
+            InputSource::File(PathBuf::from("/tmp/day03.txt"))
+        );
+        assert_eq!(
+            resolve_with(12, None, None),
+            InputSource::File(PathBuf::from(DEFAULT_INPUT_DIR).join("day12.txt"))
+        );
+    }
+
+    #[test]
+    fn default_input_exists() {
+        let source = resolve_with(1, None, None);
+        assert!(read(&source).is_ok());
+    }
+
+    #[test]
+    fn missing_file() {
+        let source = InputSource::File(PathBuf::from("does/not/exist.txt"));
+        let error = read(&source).unwrap_err();
+        assert!(error.to_string().starts_with("cannot read input from does/not/exist.txt"));
+    }
+}
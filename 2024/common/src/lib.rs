@@ -0,0 +1,7 @@
+pub mod error;
+pub mod geometry;
+pub mod input;
+pub mod solution;
+
+pub use error::{ParseError, Result};
+pub use solution::{load, load_with, run, solve, Part, Solution};
@@ -0,0 +1,164 @@
+use std::fmt;
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::input;
+
+/// A day challenge: the input is parsed once, then shared by both parts.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Output: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> Self::Output;
+    fn part_two(input: &Self::Input) -> Self::Output;
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "one"),
+            Part::Two => write!(f, "two"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// Parse `input` and run the requested parts of `S`, timing every stage.
+pub fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Report> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let value = match part {
+                Part::One => S::part_one(&input),
+                Part::Two => S::part_two(&input),
+            };
+
+            Answer { part, value: value.to_string(), elapsed: start.elapsed() }
+        })
+        .collect();
+
+    Ok(Report { parse_time, answers })
+}
+
+/// Entry point of the day binaries: read the input given on the command line
+/// and print the answer of each part.
+pub fn run<S: Solution>(parts: &[Part]) {
+    let arg = std::env::args().nth(1);
+    let (source, input) = read_or_exit(S::DAY, arg.as_deref());
+
+    match solve::<S>(&input, parts) {
+        Ok(report) => {
+            for answer in report.answers {
+                println!("Part {}: {}", answer.part, answer.value);
+            }
+        }
+        Err(error) => {
+            eprintln!("{}", error.with_file(source.to_string()));
+            process::exit(1);
+        }
+    }
+}
+
+/// Read and parse the input of `S` from `arg` (see `input::resolve`), for
+/// the day binaries needing more than `run`. Exits the process with a message
+/// when the input is missing or invalid.
+pub fn load<S: Solution>(arg: Option<&str>) -> S::Input {
+    load_with(S::DAY, arg, S::parse)
+}
+
+/// Same as `load`, for inputs in another format than the one of the puzzle.
+pub fn load_with<T>(day: u8, arg: Option<&str>, parse: impl Fn(&str) -> Result<T>) -> T {
+    let (source, input) = read_or_exit(day, arg);
+
+    parse(&input).unwrap_or_else(|error| {
+        eprintln!("{}", error.with_file(source.to_string()));
+        process::exit(1);
+    })
+}
+
+fn read_or_exit(day: u8, arg: Option<&str>) -> (input::InputSource, String) {
+    let source = input::resolve(day, arg);
+
+    match input::read(&source) {
+        Ok(input) => (source, input),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::parse_token;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+
+        type Input = Vec<u32>;
+        type Output = u32;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            input.split(',').map(|x| parse_token(1, input, x)).collect()
+        }
+
+        fn part_one(input: &Self::Input) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part_two(input: &Self::Input) -> u32 {
+            input.iter().product()
+        }
+    }
+
+    #[test]
+    fn solve_test() {
+        let report = solve::<Sum>("2,3,4", &Part::ALL).unwrap();
+        let answers: Vec<_> = report.answers.iter().map(|a| (a.part, a.value.as_str())).collect();
+        assert_eq!(answers, vec![(Part::One, "9"), (Part::Two, "24")]);
+
+        let report = solve::<Sum>("2,3,4", &[Part::Two]).unwrap();
+        assert_eq!(report.answers.len(), 1);
+        assert_eq!(report.answers[0].part, Part::Two);
+    }
+
+    #[test]
+    fn solve_parse_error() {
+        let error = solve::<Sum>("2,x", &Part::ALL).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use common::error::parse_token;
+use common::{ParseError, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = (Vec<u32>, Vec<u32>);
+    type Output = u32;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        get_inputs(input)
+    }
+
+    fn part_one((left, right): &Self::Input) -> u32 {
+        part_one(left, right)
+    }
+
+    fn part_two((left, right): &Self::Input) -> u32 {
+        part_two(left, right)
+    }
+}
+
+fn part_one(left: &[u32], right: &[u32]) -> u32 {
+    let mut left = left.to_vec();
+    let mut right = right.to_vec();
+
+    left.sort();
+    right.sort();
+
+    left.iter()
+        .zip(right.iter())
+        .map(|(l, r)| l.abs_diff(*r))
+        .sum()
+}
+
+fn part_two(left: &[u32], right: &[u32]) -> u32 {
+    let mut id_values = HashMap::<u32, u32>::new();
+
+    for id in right {
+        *id_values.entry(*id).or_insert(0) += 1;
+    }
+
+    left.iter()
+        .map(|x| x * id_values.get(x).unwrap_or(&0))
+        .sum()
+}
+
+fn get_inputs(input: &str) -> common::Result<(Vec<u32>, Vec<u32>)> {
+    input
+        .trim_end()
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            if tokens.len() != 2 {
+                let message = format!("expected two location ids, found {}", tokens.len());
+                return Err(ParseError::line(index + 1, line, message));
+            }
+
+            let left: u32 = parse_token(index + 1, line, tokens[0])?;
+            let right: u32 = parse_token(index + 1, line, tokens[1])?;
+
+            Ok((left, right))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_inputs_test() {
+        let (left, right) = get_inputs("3   4\r\n4   3\r\n2   5\n").unwrap();
+        assert_eq!(left, vec![3, 4, 2]);
+        assert_eq!(right, vec![4, 3, 5]);
+        assert_eq!(part_one(&left, &right), 3);
+        assert_eq!(part_two(&left, &right), 7);
+    }
+
+    #[test]
+    fn get_inputs_errors() {
+        let error = get_inputs("3   4\n\n2   5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = get_inputs("3   4\n4   x3").unwrap_err();
+        assert_eq!((error.line, error.column, error.width), (2, 5, 2));
+
+        let error = get_inputs("3   4   5").unwrap_err();
+        assert_eq!(error.message, "expected two location ids, found 3");
+    }
+}
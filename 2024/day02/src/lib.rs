@@ -0,0 +1,132 @@
+use common::error::parse_token;
+use common::{ParseError, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Vec<u32>>;
+    type Output = usize;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        get_input_lines(input)
+    }
+
+    fn part_one(input: &Self::Input) -> usize {
+        input.iter()
+            .filter(|level| valid_levels(level.to_vec(), false))
+            .count()
+    }
+
+    fn part_two(input: &Self::Input) -> usize {
+        input.iter()
+            .filter(|level| valid_levels(level.to_vec(), true))
+            .count()
+    }
+}
+
+fn valid_levels(levels: Vec<u32>, tolerance: bool) -> bool {
+    if tolerance {
+        let mut index = 0;
+        while index < levels.len() {
+            if do_valid_levels(levels.clone(), Some(index)) {
+                return true;
+            }
+            index += 1;
+        }
+    } else {
+        if do_valid_levels(levels.clone(), None) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn do_valid_levels(mut levels: Vec<u32>, exclude: Option<usize>) -> bool {
+    if let Some(index) = exclude {
+        levels.remove(index);
+    }
+
+    if levels.len() < 3 {
+        return true;
+    }
+
+    let ordering = levels[0].cmp(&levels[1]);
+    let mut index = 1;
+
+    while index < levels.len() {
+        let val_a = levels[index - 1];
+        let val_b = levels[index];
+
+        if val_a.cmp(&val_b) != ordering {
+            return false;
+        }
+
+        if val_a.abs_diff(val_b) > 3 {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+fn get_input_lines(input: &str) -> common::Result<Vec<Vec<u32>>> {
+    input
+        .trim_end()
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if line.trim().is_empty() {
+                return Err(ParseError::line(index + 1, line, "expected a report, found an empty line"));
+            }
+
+            line.split_whitespace()
+                .map(|num| parse_token(index + 1, line, num))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe() {
+        let data = vec![7, 6, 4, 2, 1];
+        assert!(valid_levels(data, true));
+
+        let data = vec![1, 3, 2, 4, 5];
+        assert!(valid_levels(data, true));
+
+        let data = vec![8, 6, 4, 4, 1];
+        assert!(valid_levels(data, true));
+
+        let data = vec![1, 3, 6, 7, 9];
+        assert!(valid_levels(data, true));
+    }
+
+    #[test]
+    fn get_input_lines_test() {
+        assert_eq!(get_input_lines("7 6 4\r\n1 3\n\n").unwrap(), vec![vec![7, 6, 4], vec![1, 3]]);
+
+        let error = get_input_lines("7 6 4\n\n1 3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = get_input_lines("7 6 4\n1 -3").unwrap_err();
+        assert_eq!((error.line, error.column, error.width), (2, 3, 2));
+    }
+
+    #[test]
+    fn unsafes() {
+        let data = vec![1, 2, 7, 8, 9];
+        assert!(!valid_levels(data, true));
+
+        let data = vec![9, 7, 6, 2, 1];
+        assert!(!valid_levels(data, true));
+    }
+}
@@ -0,0 +1,164 @@
+//! Search many words at once with an Aho-Corasick automaton: every line of the
+//! grid, in each of the eight directions, is read once whatever the number of
+//! words.
+
+use std::collections::{HashMap, VecDeque};
+
+use grid::{Compass, Grid, Position};
+
+use crate::Match;
+
+const ROOT: usize = 0;
+
+pub struct Dictionary<'w> {
+    words: Vec<&'w str>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Default)]
+struct Node {
+    next: HashMap<char, usize>,
+    /// The node of the longest proper suffix of this one which is in the trie.
+    fail: usize,
+    /// The words ending on this node, suffixes included.
+    outputs: Vec<usize>,
+}
+
+impl<'w> Dictionary<'w> {
+    /// The empty words are ignored.
+    pub fn new(words: &[&'w str]) -> Dictionary<'w> {
+        let mut nodes = vec![Node::default()];
+
+        for (index, word) in words.iter().enumerate().filter(|(_, word)| !word.is_empty()) {
+            let mut node = ROOT;
+
+            for char in word.chars() {
+                node = match nodes[node].next.get(&char) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[node].next.insert(char, next);
+                        next
+                    }
+                };
+            }
+
+            nodes[node].outputs.push(index);
+        }
+
+        // Breadth first, so the fail node of a node is complete before it.
+        let mut queue: VecDeque<usize> = nodes[ROOT].next.values().copied().collect();
+
+        while let Some(node) = queue.pop_front() {
+            let edges: Vec<(char, usize)> = nodes[node].next.iter().map(|(&char, &next)| (char, next)).collect();
+
+            for (char, next) in edges {
+                let fail = if node == ROOT { ROOT } else { Self::transition(&nodes, nodes[node].fail, char) };
+                let inherited = nodes[fail].outputs.clone();
+
+                nodes[next].fail = fail;
+                nodes[next].outputs.extend(inherited);
+                queue.push_back(next);
+            }
+        }
+
+        Dictionary { words: words.to_vec(), nodes }
+    }
+
+    /// Every word of the dictionary in `text`, as the index of its first
+    /// character and the index of the word, by end then longest first.
+    pub fn scan(&self, text: impl IntoIterator<Item = char>) -> Vec<(usize, usize)> {
+        let mut hits = Vec::new();
+        let mut node = ROOT;
+
+        for (end, char) in text.into_iter().enumerate() {
+            node = Self::transition(&self.nodes, node, char);
+
+            for &index in &self.nodes[node].outputs {
+                hits.push((end + 1 - self.words[index].chars().count(), index));
+            }
+        }
+
+        hits
+    }
+
+    /// Same as `find_words`, in the same order.
+    pub fn find(&self, grid: &Grid<char>) -> Vec<Match<'w>> {
+        let mut matches = Vec::new();
+
+        for direction in Compass::ALL {
+            // Every line starts on a cell with no cell behind it.
+            let starts = grid.positions().filter(|&position| !grid.contains(position.next(direction.opposite())));
+
+            for start in starts {
+                let line: Vec<(Position, char)> = grid.ray(start, direction).map(|(position, &char)| (position, char)).collect();
+
+                for (start, index) in self.scan(line.iter().map(|&(_, char)| char)) {
+                    let word = self.words[index];
+
+                    if word.chars().count() == 1 && direction != Compass::Right {
+                        continue;
+                    }
+
+                    matches.push((Match { word, start: line[start].0, direction }, index));
+                }
+            }
+        }
+
+        matches.sort_by_key(|(found, index)| (found.start.y, found.start.x, found.direction as usize, *index));
+
+        matches.into_iter().map(|(found, _)| found).collect()
+    }
+
+    fn transition(nodes: &[Node], mut node: usize, char: char) -> usize {
+        loop {
+            if let Some(&next) = nodes[node].next.get(&char) {
+                return next;
+            }
+
+            if node == ROOT {
+                return ROOT;
+            }
+
+            node = nodes[node].fail;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_words, SAMPLE};
+
+    #[test]
+    fn scan_text() {
+        let dictionary = Dictionary::new(&["he", "she", "his", "hers", ""]);
+
+        assert_eq!(dictionary.scan("ushers".chars()), vec![(1, 1), (2, 0), (2, 3)]);
+        assert_eq!(dictionary.scan("ahishe".chars()), vec![(1, 2), (3, 1), (4, 0)]);
+        assert!(dictionary.scan("".chars()).is_empty());
+    }
+
+    #[test]
+    fn same_as_word_search() {
+        let grid = Grid::parse(SAMPLE, |_, char| Ok(char)).unwrap();
+        let words = ["XMAS", "MAS", "AM", "SAMX", "X", "MAS", "MMMM", "ASAMX"];
+
+        let found = Dictionary::new(&words).find(&grid);
+
+        assert_eq!(found, find_words(&grid, &words));
+        assert_eq!(found.iter().filter(|found| found.word == "XMAS").count(), 18);
+    }
+
+    #[test]
+    fn duplicated_words_keep_their_order() {
+        let grid = Grid::parse("AA", |_, char| Ok(char)).unwrap();
+        let words = ["AA", "A", "AA"];
+
+        let found = Dictionary::new(&words).find(&grid);
+
+        assert_eq!(found, find_words(&grid, &words));
+        assert_eq!(found.iter().map(|found| found.word).collect::<Vec<_>>(), vec!["AA", "A", "AA", "A", "AA", "AA"]);
+    }
+}
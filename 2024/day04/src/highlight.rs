@@ -0,0 +1,125 @@
+//! Show the cells used by the matches, to debug a count: the letter grid with
+//! ANSI colours for the terminal, or a PPM heatmap where the more matches use
+//! a cell, the brighter it is.
+
+use grid::{Grid, Position};
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const YELLOW: &str = "\x1b[1;33m";
+const RED: &str = "\x1b[1;31m";
+
+/// How many of `matches`, each given by its cells, use every cell of `grid`.
+pub fn heat<M: IntoIterator<Item = Position>>(grid: &Grid<char>, matches: impl IntoIterator<Item = M>) -> Grid<u32> {
+    let mut heat = grid.map(|_| 0);
+
+    for cells in matches {
+        for position in cells {
+            if let Some(count) = heat.get_mut(position) {
+                *count += 1;
+            }
+        }
+    }
+
+    heat
+}
+
+/// The letters of `grid`, dimmed when no match uses them, yellow when one
+/// does, red when several do.
+pub fn ansi(grid: &Grid<char>, heat: &Grid<u32>) -> String {
+    let mut text = String::new();
+
+    for (position, &letter) in grid.iter() {
+        let colour = match heat.get(position) {
+            Some(0) | None => DIM,
+            Some(1) => YELLOW,
+            Some(_) => RED,
+        };
+
+        text.push_str(colour);
+        text.push(letter);
+
+        if position.x as usize == grid.width() - 1 {
+            text.push_str(RESET);
+            text.push('\n');
+        }
+    }
+
+    text
+}
+
+/// A binary PPM image of `heat`, `scale` pixels wide per cell, in shades of
+/// grey from black for the unused cells to white for the most used ones.
+pub fn ppm(heat: &Grid<u32>, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    let max = heat.iter().map(|(_, &count)| count).max().unwrap_or(0).max(1);
+    let (width, height) = (heat.width() * scale, heat.height() * scale);
+
+    let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    image.reserve(width * height * 3);
+
+    for row in heat.rows() {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|&count| {
+                let grey = (count * 255 / max) as u8;
+                [grey; 3].repeat(scale)
+            })
+            .collect();
+
+        for _ in 0..scale {
+            image.extend_from_slice(&line);
+        }
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("XMAS\nSAMX", |_, char| Ok(char)).unwrap()
+    }
+
+    fn line(y: i32, xs: std::ops::Range<i32>) -> Vec<Position> {
+        xs.map(|x| Position { x, y }).collect()
+    }
+
+    #[test]
+    fn heat_test() {
+        let grid = grid();
+        let heat = heat(&grid, [line(0, 0..4), line(1, 0..4), line(0, 1..3), vec![Position { x: 9, y: 9 }]]);
+
+        assert_eq!(heat.row(0), &[1, 2, 2, 1]);
+        assert_eq!(heat.row(1), &[1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn ansi_test() {
+        let grid = grid();
+        let heat = heat(&grid, [line(0, 0..2), line(0, 1..2)]);
+
+        assert_eq!(
+            ansi(&grid, &heat),
+            "\x1b[1;33mX\x1b[1;31mM\x1b[2mA\x1b[2mS\x1b[0m\n\x1b[2mS\x1b[2mA\x1b[2mM\x1b[2mX\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn ppm_test() {
+        let grid = grid();
+        let heat = heat(&grid, [line(0, 0..2), line(0, 1..2)]);
+        let image = ppm(&heat, 2);
+
+        let header = b"P6\n8 4\n255\n";
+        assert_eq!(&image[..header.len()], header);
+
+        let pixels = &image[header.len()..];
+        assert_eq!(pixels.len(), 8 * 4 * 3);
+        assert_eq!(&pixels[..12], &[127, 127, 127, 127, 127, 127, 255, 255, 255, 255, 255, 255]);
+        assert_eq!(&pixels[24..36], &pixels[..12]);
+        assert!(pixels[48..].iter().all(|&grey| grey == 0));
+    }
+}
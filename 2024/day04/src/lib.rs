@@ -0,0 +1,153 @@
+pub mod dictionary;
+pub mod highlight;
+pub mod pattern;
+pub mod search;
+
+use common::{ParseError, Part, Solution};
+use grid::{Grid, Position, Ragged};
+
+pub use dictionary::Dictionary;
+pub use pattern::{Found, Pattern};
+pub use search::{find_word, find_words, find_words_with, Edges, Match};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Grid<char>;
+    type Output = u32;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        parse_grid(input, Ragged::Reject)
+    }
+
+    fn part_one(input: &Self::Input) -> u32 {
+        get_result_part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> u32 {
+        get_result_part_two(input)
+    }
+}
+
+/// A grid of letters, one row per line, the short rows handled by `ragged`.
+/// Whitespace inside a row is an error, and so is an empty grid.
+pub fn parse_grid(input: &str, ragged: Ragged) -> common::Result<Grid<char>> {
+    let grid = Grid::parse_with(input, ragged, |_, char| {
+        if char.is_whitespace() || char.is_control() {
+            return Err(format!("unexpected {:?}, expected a letter", char));
+        }
+
+        Ok(char)
+    })?;
+
+    if grid.width() == 0 {
+        return Err(ParseError::end_of_line(1, input.lines().next().unwrap_or(""), "the grid is empty"));
+    }
+
+    Ok(grid)
+}
+
+const XMAS: &str = "XMAS";
+
+/// Two `MAS` crossing on their `A`, each written either way.
+const X_MAS: &str = "M.S/.A./M.S";
+
+/// Every `XMAS` of part one, the words handled by `edges` at the edges.
+pub fn xmas(grid: &Grid<char>, edges: Edges) -> Vec<Match<'static>> {
+    find_words_with(grid, &[XMAS], edges)
+}
+
+/// The cells of each match counted by `part`. Only the words of part one can
+/// wrap around the edges.
+pub fn matched_cells(grid: &Grid<char>, part: Part, edges: Edges) -> Vec<Vec<Position>> {
+    match part {
+        Part::One => xmas(grid, edges).iter().map(|found| found.positions().map(|position| grid.wrap(position)).collect()).collect(),
+        Part::Two => {
+            let variants = x_mas().variants();
+
+            x_mas()
+                .find(grid)
+                .iter()
+                .map(|found| variants[found.variant].letters().map(|offset| found.top_left + offset).collect())
+                .collect()
+        }
+    }
+}
+
+fn x_mas() -> Pattern {
+    Pattern::parse(X_MAS).expect("X_MAS is a valid pattern")
+}
+
+fn get_result_part_two(grid: &Grid<char>) -> u32 {
+    x_mas().find(grid).len() as u32
+}
+
+fn get_result_part_one(grid: &Grid<char>) -> u32 {
+    xmas(grid, Edges::Stop).len() as u32
+}
+
+/// The grid of the puzzle example.
+#[cfg(test)]
+pub(crate) const SAMPLE: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one() {
+        let grid = parse_grid(SAMPLE, Ragged::Reject).unwrap();
+
+        assert_eq!(crate::get_result_part_one(&grid), 18);
+        assert_eq!(crate::get_result_part_two(&grid), 9);
+    }
+
+    #[test]
+    fn matched_cells_test() {
+        let grid = parse_grid(SAMPLE, Ragged::Reject).unwrap();
+
+        assert_eq!(matched_cells(&grid, Part::One, Edges::Stop).len(), 18);
+
+        let cells = matched_cells(&grid, Part::Two, Edges::Stop);
+        assert_eq!(cells.len(), 9);
+        assert!(cells.iter().all(|cells| cells.len() == 5 && cells.iter().all(|&position| grid[position] != 'X')));
+
+        let cells = matched_cells(&grid, Part::One, Edges::Wrap);
+        assert!(cells.iter().flatten().all(|&position| grid.contains(position)));
+    }
+
+    #[test]
+    fn malformed_grids() {
+        let crlf = format!("{}\r\n\r\n", SAMPLE.replace('\n', "\r\n"));
+        assert_eq!(parse_grid(&crlf, Ragged::Reject), parse_grid(SAMPLE, Ragged::Reject));
+
+        let error = parse_grid("XMAS\nXMA\nXMAS", Ragged::Reject).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.message, "expected 4 cases on this line, found 3");
+
+        let grid = parse_grid("XMAS\nXMA\nXMAS", Ragged::Pad('.')).unwrap();
+        assert_eq!(grid.row(1), &['X', 'M', 'A', '.']);
+        assert_eq!(crate::get_result_part_one(&grid), 2);
+
+        let error = parse_grid("XMAS\nX AS", Ragged::Reject).unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (2, 2, "unexpected ' ', expected a letter"));
+
+        let error = parse_grid("XM\rAS", Ragged::Reject).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+
+        assert_eq!(parse_grid("\r\n\n", Ragged::Reject).unwrap_err().message, "the grid is empty");
+    }
+}
+
+// 2517
@@ -1,154 +1,122 @@
-use std::fs::File;
-use std::io::prelude::*;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use common::{Part, Solution};
+use day04::{highlight, Day04, Edges};
+
+const USAGE: &str = "Usage: day04 [PATH|-] [--wrap] [--highlight] [--ppm <FILE> [--scale <N>]] [--part <one|two>]
+
+  --wrap          let the words of part one continue on the opposite edge
+  --highlight     print the grid with the cells of the matches coloured
+  --ppm <FILE>    write a heatmap of the cells used by the matches to FILE
+  --scale <N>     size of a cell in the heatmap, in pixels (default: 4)
+  --part <PART>   the part whose matches are shown (default: one)";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    path: Option<String>,
+    edges: Edges,
+    highlight: bool,
+    ppm: Option<PathBuf>,
+    scale: usize,
+    part: Part,
+}
 
 fn main() {
-    let input = read_file_inputs("./inputs/day04.txt");
-    println!("Part one: {}", get_result_part_one(&input));
-    println!("Part two: {}", get_result_part_two(&input));
-}
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = parse_args(&args).unwrap_or_else(|message| {
+        eprintln!("{}\n\n{}", message, USAGE);
+        process::exit(2);
+    });
 
-fn get_result_part_two(input: &str) -> u32 {
-    let mut result: u32 = 0;
-    let line_count: usize = input.lines().count();
-    let input = input.replace("\n", "");
-    let line_len = input.len() / line_count;
-    let input = input.as_bytes();
-
-    for row in 1..line_count - 1 {
-        for col in 1..line_len - 1 {
-            let char = input[col + row * line_len];
-            if char != b'A' { continue; }
-
-            let top_left = input[(col - 1) + (row - 1) * line_len];
-            let bottom_right = input[(col + 1) + (row + 1) * line_len];
-            if !((top_left == b'M' && bottom_right == b'S') || (top_left == b'S' && bottom_right == b'M')) {
-                continue;
-            }
+    let grid = common::load::<Day04>(options.path.as_deref());
 
-            let top_right = input[(col + 1) + (row - 1) * line_len];
-            let bottom_left = input[(col - 1) + (row + 1) * line_len];
+    if options.highlight || options.ppm.is_some() {
+        let heat = highlight::heat(&grid, day04::matched_cells(&grid, options.part, options.edges));
 
-            if !((top_right == b'M' && bottom_left == b'S') || (top_right == b'S' && bottom_left == b'M')) {
-                continue;
-            }
+        if options.highlight {
+            print!("{}", highlight::ansi(&grid, &heat));
+        }
 
-            result += 1;
+        if let Some(path) = &options.ppm {
+            fs::write(path, highlight::ppm(&heat, options.scale)).unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                process::exit(1);
+            });
         }
     }
 
-    result
+    match options.edges {
+        Edges::Stop => println!("Part one: {}", Day04::part_one(&grid)),
+        Edges::Wrap => println!("Part one: {} (wrapping around the edges)", day04::xmas(&grid, Edges::Wrap).len()),
+    }
+    println!("Part two: {}", Day04::part_two(&grid));
 }
 
-fn get_result_part_one(input: &str) -> u32 {
-    let line_count: usize = input.lines().count();
-    let line_len: usize = input.lines().into_iter().next().unwrap().len();
-    let input = input.replace("\n", "");
-    let input_bytes = input.as_bytes();
-
-    let mut counter: u32 = 0;
-
-    for row in 0..line_count {
-        for col in 0..line_len {
-            if check_horizontal(&input_bytes, line_len, col, row) {
-                counter += 1;
-            }
-
-            if check_vertical(&input_bytes, line_len, col, row) {
-                counter += 1;
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options { path: None, edges: Edges::Stop, highlight: false, ppm: None, scale: 4, part: Part::One };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--wrap" => options.edges = Edges::Wrap,
+            "--highlight" => options.highlight = true,
+            "--ppm" => {
+                let path = args.next().ok_or("Missing file for --ppm")?;
+                options.ppm = Some(PathBuf::from(path));
             }
-
-            if check_diag_right(&input_bytes, line_len, col, row) {
-                counter += 1;
+            "--scale" => {
+                options.scale = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|&scale| scale > 0)
+                    .ok_or("--scale expects a positive number")?;
             }
-
-            if check_diag_left(&input_bytes, line_len, col, row) {
-                counter += 1;
+            "--part" => {
+                options.part = match args.next().map(String::as_str) {
+                    Some("one") => Part::One,
+                    Some("two") => Part::Two,
+                    _ => return Err("--part expects `one` or `two`".to_string()),
+                };
             }
+            _ if options.path.is_none() => options.path = Some(arg.clone()),
+            other => return Err(format!("Unknown argument `{}`", other)),
         }
     }
 
-    counter
-}
-
-fn check_horizontal(input: &[u8], line_len: usize, col: usize, row: usize) -> bool {
-    if col + 4 > line_len { return false }
-
-    let start_idx = row * line_len + col;
-    let letters = &input[start_idx..start_idx + 4];
-    check_letters(&letters)
+    Ok(options)
 }
 
-fn check_vertical(input: &[u8], line_len: usize, col: usize, row: usize) -> bool {
-    if (row + 3) * line_len + col >= input.len() { return false; }
-
-    let mut letters: Vec<u8> = Vec::new();
-
-    for i in 0..4 {
-        let char_index = (row + i) * line_len + col;
-        letters.push(input[char_index]);
-    }
-
-    check_letters(&letters)
-}
-
-fn check_diag_right(input: &[u8], line_len: usize, col: usize, row: usize) -> bool {
-    if col + 4 > line_len { return false; }
-    if (row + 4) * line_len > input.len() { return false; }
-
-    let mut letters: Vec<u8> = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    for i in 0..4 {
-        let char_index = (i + row) * line_len + col + i;
-        letters.push(input[char_index]);
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
     }
 
-    check_letters(&letters)
-}
-
-fn check_diag_left(input: &[u8], line_len: usize, col: usize, row: usize) -> bool {
-    if col < 3 { return false; }
-    if (row + 4) * line_len > input.len() { return false; }
-
-    let mut letters: Vec<u8> = Vec::new();
-
-    for i in 0..4 {
-        let char_index = (i + row) * line_len + col - i;
-        letters.push(input[char_index]);
+    #[test]
+    fn parse_args_test() {
+        let options = parse_args(&args("")).unwrap();
+        assert_eq!(options, Options { path: None, edges: Edges::Stop, highlight: false, ppm: None, scale: 4, part: Part::One });
+
+        let options = parse_args(&args("input.txt --highlight --ppm heat.ppm --scale 8 --part two")).unwrap();
+        assert_eq!(options.path, Some("input.txt".to_string()));
+        assert!(options.highlight);
+        assert_eq!(options.ppm, Some(PathBuf::from("heat.ppm")));
+        assert_eq!(options.scale, 8);
+        assert_eq!(options.part, Part::Two);
+        assert_eq!(options.edges, Edges::Stop);
+        assert_eq!(parse_args(&args("--wrap")).unwrap().edges, Edges::Wrap);
     }
 
-    check_letters(&letters)
-}
-
-fn check_letters(letters: &[u8]) -> bool {
-    letters == b"XMAS" || letters == b"SAMX"
-}
-
-fn read_file_inputs(path: &str) -> String {
-    let mut file = File::open(path).unwrap();
-    let mut inputs = String::new();
-    file.read_to_string(&mut inputs).unwrap().to_string();
-
-    inputs
-}
-
-#[cfg(test)]
-mod tests {
     #[test]
-    fn part_one() {
-        let input ="MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-
-        assert_eq!(crate::get_result_part_one(input), 18);
-        assert_eq!(crate::get_result_part_two(input), 9);
+    fn parse_args_errors() {
+        assert!(parse_args(&args("--ppm")).is_err());
+        assert!(parse_args(&args("--scale 0")).is_err());
+        assert!(parse_args(&args("--part three")).is_err());
+        assert!(parse_args(&args("a.txt b.txt")).is_err());
     }
 }
-
-// 2517
@@ -0,0 +1,162 @@
+//! Find small 2D shapes of letters in a grid, in every rotation and
+//! reflection.
+//!
+//! A pattern is written row after row, the rows separated by `/` or line
+//! breaks, with `.` matching any letter: `M.S/.A./M.S` is an X of two `MAS`.
+
+use common::ParseError;
+use grid::{Grid, Position};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pattern {
+    /// `None` for the wildcards.
+    cells: Grid<Option<char>>,
+}
+
+/// A pattern found in the grid, `variant` being its index in
+/// `Pattern::variants`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Found {
+    pub top_left: Position,
+    pub variant: usize,
+}
+
+impl Pattern {
+    pub fn parse(text: &str) -> common::Result<Pattern> {
+        let text = text.trim().replace('/', "\n");
+        let cells = Grid::parse(&text, |_, char| Ok(if char == '.' { None } else { Some(char) }))?;
+
+        if cells.width() == 0 {
+            return Err(ParseError::end_of_line(1, "", "the pattern is empty"));
+        }
+
+        Ok(Pattern { cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Offsets of the cells which are not wildcards, from the top left corner.
+    pub fn letters(&self) -> impl Iterator<Item = Position> + '_ {
+        self.cells.iter().filter(|(_, cell)| cell.is_some()).map(|(offset, _)| offset)
+    }
+
+    /// The pattern turned a quarter clockwise.
+    pub fn rotate(&self) -> Pattern {
+        let (width, height) = (self.width() as i32, self.height() as i32);
+        let cells = (0..width)
+            .flat_map(|y| (0..height).map(move |x| Position { x: y, y: height - 1 - x }))
+            .map(|position| self.cells[position])
+            .collect();
+
+        Pattern { cells: Grid::from_cells(height as usize, cells) }
+    }
+
+    /// The pattern mirrored left to right.
+    pub fn reflect(&self) -> Pattern {
+        let width = self.width() as i32;
+        let cells = self.cells.positions().map(|position| self.cells[Position { x: width - 1 - position.x, y: position.y }]).collect();
+
+        Pattern { cells: Grid::from_cells(self.width(), cells) }
+    }
+
+    /// The pattern in its four rotations, then mirrored, without the ones a
+    /// symmetry makes identical.
+    pub fn variants(&self) -> Vec<Pattern> {
+        let mut variants: Vec<Pattern> = Vec::new();
+        let mut variant = self.clone();
+
+        for index in 0..8 {
+            if index == 4 {
+                variant = variant.reflect();
+            }
+
+            if !variants.contains(&variant) {
+                variants.push(variant.clone());
+            }
+
+            variant = variant.rotate();
+        }
+
+        variants
+    }
+
+    /// Whether the pattern, as is, is written with its top left corner on
+    /// `top_left`.
+    pub fn matches_at(&self, grid: &Grid<char>, top_left: Position) -> bool {
+        self.cells.iter().all(|(offset, cell)| match cell {
+            None => grid.contains(top_left + offset),
+            Some(letter) => grid.get(top_left + offset) == Some(letter),
+        })
+    }
+
+    /// Every occurrence of one of the variants, row after row.
+    pub fn find(&self, grid: &Grid<char>) -> Vec<Found> {
+        let variants = self.variants();
+
+        grid.positions()
+            .flat_map(|top_left| {
+                variants
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, variant)| variant.matches_at(grid, top_left))
+                    .map(move |(variant, _)| Found { top_left, variant })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, |_, char| Ok(char)).unwrap()
+    }
+
+    #[test]
+    fn transforms() {
+        let pattern = Pattern::parse("AB.\nCDE").unwrap();
+
+        assert_eq!(pattern.rotate(), Pattern::parse("CA/DB/E.").unwrap());
+        assert_eq!(pattern.reflect(), Pattern::parse(".BA/EDC").unwrap());
+        assert_eq!(pattern.rotate().rotate().rotate().rotate(), pattern);
+        assert_eq!(pattern.variants().len(), 8);
+
+        let letters: Vec<Position> = pattern.letters().collect();
+        assert_eq!(letters, [(0, 0), (1, 0), (0, 1), (1, 1), (2, 1)].map(|(x, y)| Position { x, y }));
+    }
+
+    #[test]
+    fn symmetric_variants() {
+        assert_eq!(Pattern::parse("M.S/.A./M.S").unwrap().variants().len(), 4);
+        assert_eq!(Pattern::parse("A.A/.A./A.A").unwrap().variants().len(), 1);
+        assert_eq!(Pattern::parse("AB").unwrap().variants().len(), 4);
+    }
+
+    #[test]
+    fn find_shapes() {
+        let grid = grid("M.M.\n.A..\nS.SS\n.AS.");
+        let found = Pattern::parse("M.S/.A./M.S").unwrap().find(&grid);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].top_left, Position { x: 0, y: 0 });
+
+        // A wildcard still needs a letter under it.
+        let corner = Pattern::parse("S./.A").unwrap();
+        assert!(corner.matches_at(&grid, Position { x: 0, y: 2 }));
+        assert!(!corner.reflect().matches_at(&grid, Position { x: 3, y: 2 }));
+        assert_eq!(corner.find(&grid).len(), 4);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Pattern::parse(" ").unwrap_err().message, "the pattern is empty");
+        assert_eq!(Pattern::parse("AB/C").unwrap_err().line, 2);
+    }
+}
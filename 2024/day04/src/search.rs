@@ -0,0 +1,214 @@
+//! Find words written in a grid of letters, in any of the eight directions.
+//! The words either stop at the edges of the grid, or wrap around them like on
+//! a torus.
+
+use std::collections::hash_map::{Entry, HashMap};
+
+use grid::{Compass, Grid, Position};
+
+/// What happens to a word reaching an edge of the grid.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum Edges {
+    #[default]
+    Stop,
+    /// Continue on the opposite edge: the first column follows the last one,
+    /// and the first row the last one.
+    Wrap,
+}
+
+/// A word found in the grid, read from `start` towards `direction`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Match<'w> {
+    pub word: &'w str,
+    pub start: Position,
+    pub direction: Compass,
+}
+
+impl Match<'_> {
+    /// The cells of the word, first letter first. They may leave the grid for
+    /// a match wrapping around it, see `Grid::wrap`.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        let mut position = self.start;
+
+        self.word.chars().map(move |_| {
+            let current = position;
+            position = position.next(self.direction);
+            current
+        })
+    }
+}
+
+/// Every occurrence of `word`, row after row, then clockwise from `Up`. A
+/// palindrome is found twice, once each way; a one letter word only once per
+/// cell, facing `Right`.
+pub fn find_word<'w>(grid: &Grid<char>, word: &'w str) -> Vec<Match<'w>> {
+    find_words(grid, &[word])
+}
+
+/// Every occurrence of each of `words`, in the order of `find_word`, the words
+/// of a same cell and direction in the order given.
+pub fn find_words<'w>(grid: &Grid<char>, words: &[&'w str]) -> Vec<Match<'w>> {
+    find_words_with(grid, words, Edges::Stop)
+}
+
+/// Same as `find_words`, with the words handled by `edges` at the edges of the
+/// grid. On a small torus a word can be read on the same cells from several
+/// starts or directions, only one of those matches is kept: the first one
+/// along a row or a column, or else the first one.
+pub fn find_words_with<'w>(grid: &Grid<char>, words: &[&'w str], edges: Edges) -> Vec<Match<'w>> {
+    let mut matches: Vec<Match> = Vec::new();
+    let mut seen: HashMap<(&str, Vec<Position>), usize> = HashMap::new();
+
+    for start in grid.positions() {
+        for direction in Compass::ALL {
+            for &word in words {
+                let found = Match { word, start, direction };
+
+                if !is_written(grid, word, start, direction, edges) {
+                    continue;
+                }
+
+                if edges == Edges::Wrap {
+                    let cells: Vec<Position> = found.positions().map(|position| grid.wrap(position)).collect();
+
+                    match seen.entry((word, cells)) {
+                        Entry::Occupied(entry) => {
+                            let kept = &mut matches[*entry.get()];
+                            if kept.direction.is_diagonal() && !direction.is_diagonal() {
+                                *kept = found;
+                            }
+                            continue;
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(matches.len());
+                        }
+                    }
+                }
+
+                matches.push(found);
+            }
+        }
+    }
+
+    matches
+}
+
+fn is_written(grid: &Grid<char>, word: &str, start: Position, direction: Compass, edges: Edges) -> bool {
+    let length = word.chars().count();
+
+    if length == 0 || (length == 1 && direction != Compass::Right) {
+        return false;
+    }
+
+    match edges {
+        Edges::Stop => grid.ray(start, direction).map(|(_, &letter)| letter).take(length).eq(word.chars()),
+        Edges::Wrap => (0..length as i32)
+            .map(|index| grid[grid.wrap(start + direction.delta() * index)])
+            .eq(word.chars()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, |_, char| Ok(char)).unwrap()
+    }
+
+    #[test]
+    fn every_direction() {
+        let grid = grid("S..S..S\n.A.A.A.\n..MMM..\nSAMXMAS\n..MMM..\n.A.A.A.\nS..S..S");
+        let matches = find_word(&grid, "XMAS");
+
+        assert_eq!(matches.len(), 8);
+        assert!(matches.iter().all(|found| found.start == Position { x: 3, y: 3 }));
+
+        let directions: Vec<Compass> = matches.iter().map(|found| found.direction).collect();
+        assert_eq!(directions, Compass::ALL);
+
+        let positions: Vec<Position> = matches[1].positions().collect();
+        assert_eq!(positions, [(3, 3), (4, 2), (5, 1), (6, 0)].map(|(x, y)| Position { x, y }));
+    }
+
+    #[test]
+    fn several_words() {
+        let grid = grid("CAT\nOXO\nWOW");
+        let matches = find_words(&grid, &["CAT", "COW", "WOW", "X", ""]);
+
+        let found: Vec<(&str, Position, Compass)> = matches.iter().map(|found| (found.word, found.start, found.direction)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("CAT", Position { x: 0, y: 0 }, Compass::Right),
+                ("COW", Position { x: 0, y: 0 }, Compass::Down),
+                ("X", Position { x: 1, y: 1 }, Compass::Right),
+                ("WOW", Position { x: 0, y: 2 }, Compass::Right),
+                ("WOW", Position { x: 2, y: 2 }, Compass::Left),
+            ]
+        );
+
+        assert!(find_word(&grid, "CATS").is_empty());
+    }
+
+    fn wrapped(grid: &Grid<char>, found: &Match) -> Vec<Position> {
+        found.positions().map(|position| grid.wrap(position)).collect()
+    }
+
+    #[test]
+    fn wrap_across_edges() {
+        let row = grid("MASX\n....");
+
+        assert!(find_words(&row, &["XMAS"]).is_empty());
+
+        let found = find_words_with(&row, &["XMAS"], Edges::Wrap);
+        assert_eq!(found, vec![Match { word: "XMAS", start: Position { x: 3, y: 0 }, direction: Compass::Right }]);
+
+        let column = grid("S\nX\nM\nA");
+        let found = find_words_with(&column, &["XMAS"], Edges::Wrap);
+        assert_eq!(found.len(), 1);
+        assert_eq!(wrapped(&column, &found[0]), [(0, 1), (0, 2), (0, 3), (0, 0)].map(|(x, y)| Position { x, y }));
+    }
+
+    #[test]
+    fn wrap_across_corners() {
+        let square = grid("M...\n.A..\n..S.\n...X");
+
+        assert!(find_words(&square, &["XMAS"]).is_empty());
+
+        let found = find_words_with(&square, &["XMAS"], Edges::Wrap);
+        assert_eq!(found, vec![Match { word: "XMAS", start: Position { x: 3, y: 3 }, direction: Compass::DownRight }]);
+        assert_eq!(wrapped(&square, &found[0]), [(3, 3), (0, 0), (1, 1), (2, 2)].map(|(x, y)| Position { x, y }));
+
+        // Not a square: the diagonal comes back on the first column and row.
+        let rectangle = grid("M....\n.A...\n..S.X");
+        let found = find_words_with(&rectangle, &["XMAS"], Edges::Wrap);
+        assert_eq!(found, vec![Match { word: "XMAS", start: Position { x: 4, y: 2 }, direction: Compass::DownRight }]);
+    }
+
+    #[test]
+    fn wrap_sample() {
+        let sample = grid(SAMPLE);
+
+        assert_eq!(find_words(&sample, &["XMAS"]).len(), 18);
+        assert_eq!(find_words_with(&sample, &["XMAS"], Edges::Wrap).len(), 21);
+    }
+
+    #[test]
+    fn wrap_deduplicates() {
+        // On a single row, the diagonals read the same cells as the row.
+        let line = grid("XMAS");
+        let found = find_words_with(&line, &["XMAS", "XMASXMAS", "SAMX"], Edges::Wrap);
+        let words: Vec<(&str, Compass)> = found.iter().map(|found| (found.word, found.direction)).collect();
+        assert_eq!(words, vec![("XMAS", Compass::Right), ("XMASXMAS", Compass::Right), ("SAMX", Compass::Left)]);
+
+        let cell = grid("A");
+        assert_eq!(find_words_with(&cell, &["AAA"], Edges::Wrap).len(), 1);
+        assert!(find_words(&cell, &["AAA"]).is_empty());
+
+        // A palindrome is still found both ways.
+        let palindrome = grid("ABA.");
+        assert_eq!(find_words_with(&palindrome, &["ABA"], Edges::Wrap).len(), 2);
+    }
+}
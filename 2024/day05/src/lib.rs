@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+
+use common::error::parse_token;
+use common::{ParseError, Solution};
+
+/// For each page, the pages that must be printed after it.
+type Orders = HashMap<i32, Vec<i32>>;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = (Orders, Vec<Vec<i32>>);
+    type Output = i32;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        get_inputs(input)
+    }
+
+    fn part_one((orders, updates): &Self::Input) -> i32 {
+        updates.iter()
+            .filter(|update| is_valid_update(update, orders))
+            .filter_map(|update| get_mid_value(update))
+            .sum()
+    }
+
+    fn part_two((orders, updates): &Self::Input) -> i32 {
+        updates.iter()
+            .filter(|update| !is_valid_update(update, orders))
+            .map(|x| fix_update(x, orders))
+            .filter_map(|update| get_mid_value(&update))
+            .sum()
+    }
+}
+
+fn parse_order_lines<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> common::Result<Orders> {
+    let mut map = HashMap::new();
+
+    for (index, line) in lines {
+        let (a, b) = line
+            .split_once("|")
+            .ok_or_else(|| ParseError::line(index + 1, line, "expected a page ordering rule like `47|53`"))?;
+        let a: i32 = parse_token(index + 1, line, a)?;
+        let b: i32 = parse_token(index + 1, line, b)?;
+
+        map.entry(a).or_insert_with(Vec::new).push(b);
+    }
+
+    Ok(map)
+}
+
+fn parse_update_lines<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> common::Result<Vec<Vec<i32>>> {
+    lines
+        .map(|(index, line)| {
+            line.split(",").map(|x| parse_token(index + 1, line, x)).collect()
+        }).collect()
+}
+
+fn is_valid_update(update: &[i32], orders: &Orders) -> bool {
+    let mut history = HashSet::new();
+
+    for page in update {
+        if let Some(dependencies) = orders.get(page) {
+            if dependencies.iter().any(|x| history.contains(&x)) {
+                return false;
+            }
+        }
+
+        history.insert(page);
+    }
+
+    true
+}
+
+fn fix_update(update: &[i32], orders: &Orders) -> Vec<i32> {
+    let mut update = update.to_vec();
+    let mut result: Vec<i32> = Vec::new();
+    let mut reversed_orders = HashMap::new();
+
+    for (value, dependencies) in orders {
+        for dependency in dependencies {
+            reversed_orders.entry(dependency).or_insert(Vec::new()).push(value);
+        }
+    }
+
+    while !update.is_empty() {
+        for i in 0..update.len() {
+            let value = update[i];
+
+            if let Some(dependants) = reversed_orders.get(&value) {
+                if has_dependants(dependants, &update) {
+                    continue;
+                }
+            }
+
+            update.remove(i);
+            result.push(value);
+            break;
+        }
+    }
+
+    result
+}
+
+fn has_dependants(dependants: &[&i32], values: &[i32]) -> bool {
+    for dependency in dependants {
+        if values.contains(dependency) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn get_mid_value(update: &[i32]) -> Option<i32> {
+    if update.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let position = update.len() / 2;
+
+    update.get(position).copied()
+}
+
+fn get_inputs(input: &str) -> common::Result<(Orders, Vec<Vec<i32>>)> {
+    let mut lines = input.trim_end().lines().enumerate();
+    let order_lines: Vec<(usize, &str)> = lines.by_ref().take_while(|(_, line)| !line.is_empty()).collect();
+    let update_lines: Vec<(usize, &str)> = lines.collect();
+
+    let orders = parse_order_lines(order_lines.iter().copied())?;
+
+    if update_lines.is_empty() {
+        let (index, line) = order_lines.last().copied().unwrap_or((0, ""));
+        return Err(ParseError::end_of_line(
+            index + 1,
+            line,
+            "expected an empty line between the page ordering rules and the updates",
+        ));
+    }
+
+    let updates = parse_update_lines(update_lines.into_iter())?;
+
+    Ok((orders, updates))
+}
+
+#[cfg(test)]
+mod tests {
+    // const INPUT: &str = "75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
+    const ORDER_INPUT: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n\
+                               97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13";
+
+    use super::*;
+
+    fn parse_order_input(input: &str) -> common::Result<Orders> {
+        parse_order_lines(input.lines().enumerate())
+    }
+
+    #[test]
+    fn parse_order_input_test() {
+        let map = parse_order_input("47|53\n97|13\n97|61").unwrap();
+        assert_eq!(map.get(&47), Some(&vec![53]));
+        assert_eq!(map.get(&97), Some(&vec![13, 61]));
+    }
+
+    #[test]
+    fn get_inputs_test() {
+        let (orders, updates) = get_inputs("47|53\r\n97|13\r\n\r\n75,47,61\r\n97,61\r\n").unwrap();
+        assert_eq!(orders.get(&97), Some(&vec![13]));
+        assert_eq!(updates, vec![vec![75, 47, 61], vec![97, 61]]);
+    }
+
+    #[test]
+    fn get_inputs_errors() {
+        let error = get_inputs("47|53\n97-13\n\n75,47,61").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = get_inputs("47|53\n\n75,47,,61").unwrap_err();
+        assert_eq!((error.line, error.column, error.width), (3, 7, 0));
+
+        let error = get_inputs("47|53\n97|13").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
+
+    #[test]
+    fn is_valid_update_test() {
+        let orders = parse_order_input(ORDER_INPUT).unwrap();
+
+        // Valids
+        assert!(is_valid_update(&[75,47,61,53,29], &orders));
+        assert!(is_valid_update(&[97,61,53,29,13], &orders));
+        assert!(is_valid_update(&[75,29,13], &orders));
+
+        // Invalids
+        assert!(!is_valid_update(&[75,97,47,61,53], &orders));
+        assert!(!is_valid_update(&[61,13,29], &orders));
+        assert!(!is_valid_update(&[97,13,75,29,47], &orders));
+    }
+
+    #[test]
+    fn fix_update_test() {
+        let orders = parse_order_input(ORDER_INPUT).unwrap();
+
+        // assert_eq!(fix_update(&vec![75,97,47,61,53], &orders), vec![97,75,47,61,53]);
+        assert_eq!(fix_update(&[61,13,29], &orders), vec![61,29,13]);
+        // assert_eq!(fix_update(&vec![97,13,75,29,47], &orders), vec![47,75,47,29,13]);
+    }
+
+    #[test]
+    fn get_mid_value_test() {
+        assert_eq!(get_mid_value(&[75, 47, 61, 53, 29]), Some(61));
+        assert_eq!(get_mid_value(&[97, 61, 53, 29, 13]), Some(53));
+        assert_eq!(get_mid_value(&[75, 29, 13]), Some(29));
+    }
+}
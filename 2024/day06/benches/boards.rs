@@ -0,0 +1,86 @@
+//! Compare the `HashMap` board, the dense board and the jump table on the
+//! real input.
+//!
+//! Run with `cargo bench -p day06`.
+
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::time::{Duration, Instant};
+
+use day06::{count_looping_obstacles, parallel, parse_input, Board, DenseBoard, Compass, Guard, History, JumpTable, Position, Rules};
+
+const PATROLS: u32 = 100;
+
+fn main() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs/day06.txt");
+    let input = read_to_string(path).expect("the day06 input is needed to run the benchmark");
+    let (position, direction, grid) = parse_input(&input).expect("invalid day06 input");
+    let board = DenseBoard::from(&grid);
+    let hash_map: HashMap<Position, _> = grid.iter().map(|(position, &case)| (position, case)).collect();
+
+    let hash_map_time = bench_patrol(&hash_map, position, direction);
+    let dense = bench_patrol(&board, position, direction);
+    report("patrol", hash_map_time, dense);
+
+    let (hash_map_time, hash_map_count) = bench_loop_search(&hash_map, position, direction);
+    let (dense, dense_count) = bench_loop_search(&board, position, direction);
+    assert_eq!(hash_map_count, dense_count);
+    report("loop search", hash_map_time, dense);
+
+    let start = Instant::now();
+    let jump_count = JumpTable::new(&board).looping_obstacles(&board, position, direction, 1).len();
+    let jump = start.elapsed();
+    assert_eq!(jump_count, dense_count);
+    println!("{:<12} jump table: {:>9?}   speedup over dense: x{:.1}", "", jump, dense.as_secs_f64() / jump.as_secs_f64());
+
+    let threads = parallel::default_threads();
+
+    let start = Instant::now();
+    let parallel_count = parallel::looping_obstacles(&board, position, direction, Rules::default(), threads).len();
+    let dense_parallel = start.elapsed();
+    assert_eq!(parallel_count, dense_count);
+
+    let start = Instant::now();
+    let parallel_count = JumpTable::new(&board).looping_obstacles(&board, position, direction, threads).len();
+    let jump_parallel = start.elapsed();
+    assert_eq!(parallel_count, dense_count);
+
+    println!(
+        "{:<12} {} threads, dense: {:?} (x{:.1}), jump table: {:?} (x{:.1})",
+        "",
+        threads,
+        dense_parallel,
+        dense.as_secs_f64() / dense_parallel.as_secs_f64(),
+        jump_parallel,
+        jump.as_secs_f64() / jump_parallel.as_secs_f64()
+    );
+}
+
+fn bench_patrol<B: Board + Clone>(board: &B, position: Position, direction: Compass) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..PATROLS {
+        let mut guard = Guard::new(board.clone(), position, direction);
+        guard.patrol();
+        assert!(!guard.history.is_empty());
+    }
+
+    start.elapsed() / PATROLS
+}
+
+fn bench_loop_search<B: Board + Clone>(board: &B, position: Position, direction: Compass) -> (Duration, usize) {
+    let start = Instant::now();
+    let count = count_looping_obstacles(board, position, direction);
+
+    (start.elapsed(), count)
+}
+
+fn report(name: &str, hash_map: Duration, dense: Duration) {
+    println!(
+        "{:<12} HashMap: {:>12?}   dense: {:>12?}   speedup: x{:.1}",
+        name,
+        hash_map,
+        dense,
+        hash_map.as_secs_f64() / dense.as_secs_f64()
+    );
+}
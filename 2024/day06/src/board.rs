@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use crate::{Case, Compass, Grid, Position};
+
+/// The map the guard is walking on.
+pub trait Board {
+    type History: History;
+
+    /// The case at `position`, `None` when it is outside of the map.
+    fn case(&self, position: Position) -> Option<Case>;
+
+    fn set_case(&mut self, position: Position, case: Case);
+
+    /// Width and height of the map.
+    fn size(&self) -> (usize, usize);
+
+    /// An empty history able to hold every case of the board.
+    fn history(&self) -> Self::History;
+}
+
+/// The cases visited by the guard, and the directions it had on each of them.
+pub trait History {
+    /// Record that the guard stood on `position` facing `direction`. Returns
+    /// `false` when it already did, meaning it is looping.
+    fn visit(&mut self, position: Position, direction: Compass) -> bool;
+
+    fn contains(&self, position: Position, direction: Compass) -> bool;
+
+    /// Number of distinct visited cases.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn positions(&self) -> Vec<Position>;
+}
+
+impl Board for HashMap<Position, Case> {
+    type History = HashMap<Position, Vec<Compass>>;
+
+    fn case(&self, position: Position) -> Option<Case> {
+        self.get(&position).copied()
+    }
+
+    fn set_case(&mut self, position: Position, case: Case) {
+        self.insert(position, case);
+    }
+
+    fn size(&self) -> (usize, usize) {
+        let width = self.keys().map(|position| position.x + 1).max().unwrap_or(0);
+        let height = self.keys().map(|position| position.y + 1).max().unwrap_or(0);
+
+        (width as usize, height as usize)
+    }
+
+    fn history(&self) -> Self::History {
+        HashMap::new()
+    }
+}
+
+impl History for HashMap<Position, Vec<Compass>> {
+    fn visit(&mut self, position: Position, direction: Compass) -> bool {
+        let case_history = self.entry(position).or_default();
+
+        if case_history.contains(&direction) {
+            false
+        } else {
+            case_history.push(direction);
+            true
+        }
+    }
+
+    fn contains(&self, position: Position, direction: Compass) -> bool {
+        self.get(&position).is_some_and(|directions| directions.contains(&direction))
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn positions(&self) -> Vec<Position> {
+        self.keys().copied().collect()
+    }
+}
+
+impl Board for Grid<Case> {
+    type History = DirectionMask;
+
+    fn case(&self, position: Position) -> Option<Case> {
+        self.get(position).copied()
+    }
+
+    fn set_case(&mut self, position: Position, case: Case) {
+        if let Some(cell) = self.get_mut(position) {
+            *cell = case;
+        }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    fn history(&self) -> DirectionMask {
+        DirectionMask::new(self.width(), self.height())
+    }
+}
+
+/// A rectangular board storing obstacles as one bit per case. The few special
+/// tiles are kept aside, by index.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DenseBoard {
+    width: usize,
+    height: usize,
+    obstacles: Vec<u64>,
+    tiles: HashMap<usize, Case>,
+}
+
+impl DenseBoard {
+    pub fn new(width: usize, height: usize) -> DenseBoard {
+        DenseBoard { width, height, obstacles: vec![0; (width * height).div_ceil(64)], tiles: HashMap::new() }
+    }
+
+    /// Whether the board has cases other than free ones and obstacles.
+    pub fn has_tiles(&self) -> bool {
+        !self.tiles.is_empty()
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, position: Position) -> Option<usize> {
+        index(self.width, self.height, position)
+    }
+}
+
+impl From<&HashMap<Position, Case>> for DenseBoard {
+    fn from(grid: &HashMap<Position, Case>) -> DenseBoard {
+        let (width, height) = grid.size();
+        let mut board = DenseBoard::new(width, height);
+
+        for (position, case) in grid {
+            board.set_case(*position, *case);
+        }
+
+        board
+    }
+}
+
+impl From<&Grid<Case>> for DenseBoard {
+    fn from(grid: &Grid<Case>) -> DenseBoard {
+        let mut board = DenseBoard::new(grid.width(), grid.height());
+
+        for (position, case) in grid.iter() {
+            board.set_case(position, *case);
+        }
+
+        board
+    }
+}
+
+impl Board for DenseBoard {
+    type History = DirectionMask;
+
+    fn case(&self, position: Position) -> Option<Case> {
+        let index = self.index(position)?;
+
+        if self.obstacles[index / 64] & (1 << (index % 64)) != 0 {
+            Some(Case::Obstacle())
+        } else if self.tiles.is_empty() {
+            Some(Case::Free())
+        } else {
+            Some(self.tiles.get(&index).copied().unwrap_or(Case::Free()))
+        }
+    }
+
+    fn set_case(&mut self, position: Position, case: Case) {
+        let Some(index) = self.index(position) else { return };
+
+        if !self.tiles.is_empty() {
+            self.tiles.remove(&index);
+        }
+
+        match case {
+            Case::Obstacle() => self.obstacles[index / 64] |= 1 << (index % 64),
+            Case::Free() => self.obstacles[index / 64] &= !(1 << (index % 64)),
+            tile => {
+                self.obstacles[index / 64] &= !(1 << (index % 64));
+                self.tiles.insert(index, tile);
+            }
+        }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn history(&self) -> DirectionMask {
+        DirectionMask::new(self.width, self.height)
+    }
+}
+
+/// A history storing the visited directions of each case as one bit per
+/// direction, in one byte per case.
+#[derive(Debug, Clone)]
+pub struct DirectionMask {
+    width: usize,
+    height: usize,
+    masks: Vec<u8>,
+    visited: usize,
+}
+
+impl DirectionMask {
+    pub fn new(width: usize, height: usize) -> DirectionMask {
+        DirectionMask { width, height, masks: vec![0; width * height], visited: 0 }
+    }
+}
+
+impl History for DirectionMask {
+    fn visit(&mut self, position: Position, direction: Compass) -> bool {
+        let Some(index) = index(self.width, self.height, position) else { return true };
+        let bit = direction_bit(direction);
+        let mask = &mut self.masks[index];
+
+        if *mask & bit != 0 {
+            return false;
+        }
+
+        if *mask == 0 {
+            self.visited += 1;
+        }
+
+        *mask |= bit;
+        true
+    }
+
+    fn contains(&self, position: Position, direction: Compass) -> bool {
+        index(self.width, self.height, position)
+            .is_some_and(|index| self.masks[index] & direction_bit(direction) != 0)
+    }
+
+    fn len(&self) -> usize {
+        self.visited
+    }
+
+    fn positions(&self) -> Vec<Position> {
+        (0..self.width * self.height)
+            .filter(|&index| self.masks[index] != 0)
+            .map(|index| Position { x: (index % self.width) as i32, y: (index / self.width) as i32 })
+            .collect()
+    }
+}
+
+fn index(width: usize, height: usize, position: Position) -> Option<usize> {
+    if position.x < 0 || position.y < 0 {
+        return None;
+    }
+
+    let (x, y) = (position.x as usize, position.y as usize);
+
+    if x >= width || y >= height {
+        return None;
+    }
+
+    Some(x + y * width)
+}
+
+fn direction_bit(direction: Compass) -> u8 {
+    1 << direction as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dense_board_test() {
+        let mut grid = HashMap::new();
+        for x in 0..3 {
+            for y in 0..2 {
+                grid.insert(Position { x, y }, Case::Free());
+            }
+        }
+        grid.insert(Position { x: 1, y: 1 }, Case::Obstacle());
+
+        let board = DenseBoard::from(&grid);
+        assert_eq!(board.case(Position { x: 1, y: 1 }), Some(Case::Obstacle()));
+        assert_eq!(board.case(Position { x: 2, y: 1 }), Some(Case::Free()));
+        assert_eq!(board.case(Position { x: 3, y: 1 }), None);
+        assert_eq!(board.case(Position { x: 0, y: -1 }), None);
+        assert!(!board.has_tiles());
+
+        let mut board = board;
+        board.set_case(Position { x: 1, y: 1 }, Case::DoubleWall());
+        assert_eq!(board.case(Position { x: 1, y: 1 }), Some(Case::DoubleWall()));
+        assert!(board.has_tiles());
+
+        board.set_case(Position { x: 1, y: 1 }, Case::Obstacle());
+        assert_eq!(board.case(Position { x: 1, y: 1 }), Some(Case::Obstacle()));
+        assert!(!board.has_tiles());
+    }
+
+    #[test]
+    fn direction_mask_test() {
+        let mut history = DirectionMask::new(3, 3);
+        let position = Position { x: 1, y: 2 };
+        let other = Position { x: 2, y: 2 };
+
+        assert!(history.visit(position, Compass::Up));
+        assert!(history.visit(position, Compass::Left));
+        assert!(!history.visit(position, Compass::Up));
+        assert!(history.visit(other, Compass::Up));
+        assert!(!history.visit(other, Compass::Up));
+        assert!(history.visit(other, Compass::UpLeft));
+
+        assert!(history.contains(position, Compass::Left));
+        assert!(!history.contains(position, Compass::Down));
+        assert!(!history.contains(Position { x: 0, y: 2 }, Compass::Up));
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.positions(), vec![position, other]);
+    }
+}
@@ -0,0 +1,155 @@
+//! Describe the loop a guard is stuck in. The patrol is replayed step by step
+//! with its own bookkeeping, independently of the `Guard` history, so the
+//! result can be used to check the loop detection. Only the moves themselves
+//! come from the shared `Rules`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Board, Compass, Position, Rules};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cycle {
+    /// Step at which the guard first stood in the state it later came back
+    /// to. A step is either a move or a turn.
+    pub entry_step: usize,
+    /// Number of steps of one lap.
+    pub length: usize,
+    /// Cases of the loop, in walking order from the entry.
+    pub cells: Vec<Position>,
+    /// Obstacles the guard bounces off during one lap, in order.
+    pub obstacles: Vec<Position>,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let obstacles: Vec<String> = self.obstacles
+            .iter()
+            .map(|position| format!("({}, {})", position.x, position.y))
+            .collect();
+
+        write!(
+            f,
+            "loop entered at step {}, {} steps per lap over {} cases, bouncing off {}",
+            self.entry_step,
+            self.length,
+            self.cells.len(),
+            obstacles.join(" ")
+        )
+    }
+}
+
+/// The loop of a guard starting on `position`, `None` when it leaves the map.
+pub fn analyse<B: Board>(board: &B, position: Position, direction: Compass) -> Option<Cycle> {
+    analyse_with_rules(board, position, direction, Rules::default())
+}
+
+pub fn analyse_with_rules<B: Board>(board: &B, position: Position, direction: Compass, rules: Rules) -> Option<Cycle> {
+    let mut trace = vec![(position, direction)];
+    let mut seen = HashMap::from([((position, direction), 0)]);
+    let (mut position, mut direction) = (position, direction);
+
+    loop {
+        (position, direction) = rules.step(board, position, direction)?;
+
+        let step = trace.len();
+
+        if let Some(&entry_step) = seen.get(&(position, direction)) {
+            return Some(cycle(&trace[entry_step..], entry_step));
+        }
+
+        seen.insert((position, direction), step);
+        trace.push((position, direction));
+    }
+}
+
+fn cycle(lap: &[(Position, Compass)], entry_step: usize) -> Cycle {
+    let mut cells = Vec::new();
+    let mut obstacles = Vec::new();
+
+    for (index, &(position, direction)) in lap.iter().enumerate() {
+        if !cells.contains(&position) {
+            cells.push(position);
+        }
+
+        // The lap wraps around to its first state.
+        let (_, next_direction) = lap[(index + 1) % lap.len()];
+
+        if next_direction != direction {
+            obstacles.push(position.next(direction));
+        }
+    }
+
+    Cycle { entry_step, length: lap.len(), cells, obstacles }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{looping_obstacles, parse_input, Case, DenseBoard, Guard, SAMPLE};
+
+    #[test]
+    fn no_cycle() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+
+        assert_eq!(analyse(&grid, position, direction), None);
+    }
+
+    #[test]
+    fn square_cycle() {
+        // The guard loops around the 2x3 rectangle after its first step.
+        let (position, direction, grid) = parse_input(".#..\n...#\n....\n#...\n.^#.").unwrap();
+
+        let cycle = analyse(&grid, position, direction).unwrap();
+
+        assert_eq!(cycle.entry_step, 1);
+        assert_eq!(cycle.length, 10);
+        assert_eq!(
+            cycle.cells,
+            [(1, 3), (1, 2), (1, 1), (2, 1), (2, 2), (2, 3)].map(|(x, y)| Position { x, y })
+        );
+        assert_eq!(cycle.obstacles, [(1, 0), (3, 1), (2, 4), (0, 3)].map(|(x, y)| Position { x, y }));
+        assert_eq!(
+            cycle.to_string(),
+            "loop entered at step 1, 10 steps per lap over 6 cases, bouncing off (1, 0) (3, 1) (2, 4) (0, 3)"
+        );
+    }
+
+    #[test]
+    fn agrees_with_guard() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let board = DenseBoard::from(&grid);
+        let looping = looping_obstacles(&board, position, direction);
+
+        for y in 0..10 {
+            for x in 0..10 {
+                let candidate = Position { x, y };
+                if candidate == position {
+                    continue;
+                }
+
+                let mut board = board.clone();
+                board.set_case(candidate, Case::Obstacle());
+
+                let mut guard = Guard::new(board.clone(), position, direction);
+                guard.patrol();
+
+                let cycle = analyse(&board, position, direction);
+                assert_eq!(cycle.is_some(), guard.looping, "obstacle on {:?}", candidate);
+                assert_eq!(cycle.is_some(), looping.contains(&candidate), "obstacle on {:?}", candidate);
+            }
+        }
+    }
+
+    #[test]
+    fn turning_in_place() {
+        let (position, direction, grid) = parse_input("###\n#^#\n.#.").unwrap();
+
+        let cycle = analyse(&grid, position, direction).unwrap();
+
+        assert_eq!(cycle.entry_step, 0);
+        assert_eq!(cycle.length, 4);
+        assert_eq!(cycle.cells, vec![position]);
+        assert_eq!(cycle.obstacles.len(), 4);
+    }
+}
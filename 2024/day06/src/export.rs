@@ -0,0 +1,148 @@
+//! The step log of a patrol, written as JSON or CSV to be plotted or diffed
+//! by other tools. Both formats are small enough to be written by hand.
+
+use crate::{Board, Compass, Guard, Position, StepOutcome};
+
+/// One line of the log. The first step is the starting state, without outcome.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Step {
+    pub index: usize,
+    pub position: Position,
+    pub direction: Compass,
+    pub outcome: Option<StepOutcome>,
+}
+
+impl Step {
+    pub fn event(&self) -> &'static str {
+        match self.outcome {
+            None => "start",
+            Some(StepOutcome::Moved(_)) => "moved",
+            Some(StepOutcome::Turned(_)) => "turned",
+            Some(StepOutcome::Exited) => "exited",
+            Some(StepOutcome::LoopDetected) => "loop",
+        }
+    }
+}
+
+/// Walk the guard until the end of its patrol, logging every step with the
+/// state of the guard after it.
+pub fn log<B: Board>(guard: &mut Guard<B>) -> Vec<Step> {
+    let mut steps = vec![Step { index: 0, position: guard.position, direction: guard.direction, outcome: None }];
+
+    loop {
+        let outcome = guard.walk();
+
+        steps.push(Step {
+            index: steps.len(),
+            position: guard.position,
+            direction: guard.direction,
+            outcome: Some(outcome),
+        });
+
+        if outcome.is_final() {
+            return steps;
+        }
+    }
+}
+
+pub fn to_json(steps: &[Step]) -> String {
+    let lines: Vec<String> = steps
+        .iter()
+        .map(|step| {
+            format!(
+                "  {{\"step\": {}, \"x\": {}, \"y\": {}, \"direction\": \"{}\", \"event\": \"{}\"}}",
+                step.index,
+                step.position.x,
+                step.position.y,
+                direction_name(step.direction),
+                step.event()
+            )
+        })
+        .collect();
+
+    if lines.is_empty() {
+        return "[]\n".to_string();
+    }
+
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+pub fn to_csv(steps: &[Step]) -> String {
+    let mut csv = String::from("step,x,y,direction,event\n");
+
+    for step in steps {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            step.index,
+            step.position.x,
+            step.position.y,
+            direction_name(step.direction),
+            step.event()
+        ));
+    }
+
+    csv
+}
+
+fn direction_name(direction: Compass) -> &'static str {
+    match direction {
+        Compass::Up => "up",
+        Compass::UpRight => "up_right",
+        Compass::Right => "right",
+        Compass::DownRight => "down_right",
+        Compass::Down => "down",
+        Compass::DownLeft => "down_left",
+        Compass::Left => "left",
+        Compass::UpLeft => "up_left",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    fn steps(input: &str) -> Vec<Step> {
+        let (position, direction, grid) = parse_input(input).unwrap();
+        log(&mut Guard::new(grid, position, direction))
+    }
+
+    #[test]
+    fn log_test() {
+        let steps = steps("#.\n^.");
+
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[0], Step { index: 0, position: Position { x: 0, y: 1 }, direction: Compass::Up, outcome: None });
+        assert_eq!(steps[1].outcome, Some(StepOutcome::Turned(Compass::Right)));
+        assert_eq!(steps[2].position, Position { x: 1, y: 1 });
+        assert_eq!(steps[3].event(), "exited");
+        assert_eq!(steps[3].position, Position { x: 1, y: 1 });
+    }
+
+    #[test]
+    fn to_json_test() {
+        assert_eq!(
+            to_json(&steps("#.\n^.")),
+            "[\n  \
+             {\"step\": 0, \"x\": 0, \"y\": 1, \"direction\": \"up\", \"event\": \"start\"},\n  \
+             {\"step\": 1, \"x\": 0, \"y\": 1, \"direction\": \"right\", \"event\": \"turned\"},\n  \
+             {\"step\": 2, \"x\": 1, \"y\": 1, \"direction\": \"right\", \"event\": \"moved\"},\n  \
+             {\"step\": 3, \"x\": 1, \"y\": 1, \"direction\": \"right\", \"event\": \"exited\"}\n\
+             ]\n"
+        );
+        assert_eq!(to_json(&[]), "[]\n");
+    }
+
+    #[test]
+    fn to_csv_test() {
+        assert_eq!(
+            to_csv(&steps("###\n#^#\n###")),
+            "step,x,y,direction,event\n\
+             0,1,1,up,start\n\
+             1,1,1,right,turned\n\
+             2,1,1,down,turned\n\
+             3,1,1,left,turned\n\
+             4,1,1,left,loop\n"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use grid::Direction;
+
+use crate::{obstacle_candidates, parallel, Board, Case, Compass, DenseBoard, Position, Rules};
+
+const EXIT: u32 = u32::MAX;
+
+/// For every free case and direction, the case where the guard stops before
+/// bumping into an obstacle, so a patrol costs one iteration per turn instead
+/// of one per case. Only valid under the default rules, on a board without
+/// special tiles.
+pub struct JumpTable {
+    width: usize,
+    height: usize,
+    /// Indexed by `case * 4 + direction`, `EXIT` when the guard leaves the map.
+    jumps: Vec<u32>,
+}
+
+impl JumpTable {
+    pub fn new(board: &DenseBoard) -> JumpTable {
+        let (width, height) = (board.width(), board.height());
+        let mut jumps = vec![EXIT; width * height * 4];
+
+        for (direction_index, direction) in Direction::ALL.into_iter().enumerate() {
+            let delta = direction.delta();
+
+            // Walk every line against the direction, remembering the last
+            // case seen before an obstacle.
+            for start in 0..width.max(height) {
+                let mut stop = EXIT;
+                let mut position = match direction {
+                    Direction::Up => Position { x: start as i32, y: 0 },
+                    Direction::Down => Position { x: start as i32, y: height as i32 - 1 },
+                    Direction::Left => Position { x: 0, y: start as i32 },
+                    Direction::Right => Position { x: width as i32 - 1, y: start as i32 },
+                };
+
+                while let Some(case) = board.case(position) {
+                    let index = position.x as usize + position.y as usize * width;
+
+                    if case == Case::Free() {
+                        if board.case(position + delta) == Some(Case::Obstacle()) {
+                            stop = index as u32;
+                        }
+                        jumps[index * 4 + direction_index] = stop;
+                    }
+
+                    position -= delta;
+                }
+            }
+        }
+
+        JumpTable { width, height, jumps }
+    }
+
+    /// Whether a guard starting on `position` loops, with an optional extra
+    /// obstacle which is not part of the table.
+    pub fn loops(&self, position: Position, direction: Compass, obstacle: Option<Position>) -> bool {
+        let mut seen = vec![0u8; self.width * self.height];
+        let mut index = position.x as usize + position.y as usize * self.width;
+        let mut direction_index = direction_index(direction);
+
+        loop {
+            let mut target = self.jumps[index * 4 + direction_index];
+
+            if let Some(obstacle) = obstacle {
+                if let Some(stop) = self.blocked_by(index, direction_index, target, obstacle) {
+                    target = stop;
+                }
+            }
+
+            if target == EXIT {
+                return false;
+            }
+
+            index = target as usize;
+            let bit = 1 << direction_index;
+
+            if seen[index] & bit != 0 {
+                return true;
+            }
+
+            seen[index] |= bit;
+            direction_index = (direction_index + 1) % 4;
+        }
+    }
+
+    /// Same as `looping_obstacles`, but using the table for the patrols with
+    /// the new obstacle, over `threads` workers.
+    pub fn looping_obstacles(&self, board: &DenseBoard, position: Position, direction: Compass, threads: usize) -> Vec<Position> {
+        let candidates = obstacle_candidates(board, position, direction, Rules::default());
+
+        parallel::filter(&candidates, threads, || (), |_, candidate| self.loops(position, direction, Some(candidate)))
+    }
+
+    /// Where the guard stops when `obstacle` is between the case at `index`
+    /// and the `target` of the jump.
+    fn blocked_by(&self, index: usize, direction_index: usize, target: u32, obstacle: Position) -> Option<u32> {
+        let (x, y) = ((index % self.width) as i32, (index / self.width) as i32);
+        let Position { x: dx, y: dy } = Direction::ALL[direction_index].delta();
+        let (ox, oy) = (obstacle.x - x, obstacle.y - y);
+
+        // Distance to the obstacle along the ray, if it is on the ray.
+        let distance = match (dx, dy) {
+            (0, _) if ox == 0 && oy * dy > 0 => oy * dy,
+            (_, 0) if oy == 0 && ox * dx > 0 => ox * dx,
+            _ => return None,
+        };
+
+        let reach = if target == EXIT {
+            i32::MAX
+        } else {
+            let target = target as usize;
+            let target = Position { x: (target % self.width) as i32, y: (target / self.width) as i32 };
+            target.manhattan(Position { x, y }) as i32
+        };
+
+        if distance > reach {
+            return None;
+        }
+
+        let stop = Position { x, y } + Position { x: dx, y: dy } * (distance - 1);
+        Some((stop.x as usize + stop.y as usize * self.width) as u32)
+    }
+}
+
+fn direction_index(direction: Compass) -> usize {
+    match Direction::try_from(direction) {
+        Ok(direction) => direction as usize,
+        Err(diagonal) => panic!("the jump table has no {:?} moves", diagonal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{looping_obstacles, parse_input, Guard, SAMPLE};
+
+    fn guard_loops(board: &DenseBoard, position: Position, direction: Compass, obstacle: Option<Position>) -> bool {
+        let mut board = board.clone();
+        if let Some(obstacle) = obstacle {
+            board.set_case(obstacle, Case::Obstacle());
+        }
+
+        let mut guard = Guard::new(board, position, direction);
+        guard.patrol();
+        guard.looping
+    }
+
+    #[test]
+    fn jumps_test() {
+        let (position, _, grid) = parse_input(SAMPLE).unwrap();
+        let board = DenseBoard::from(&grid);
+        let table = JumpTable::new(&board);
+        let jump = |position: Position, direction: Compass| {
+            let index = position.x as usize + position.y as usize * 10;
+            match table.jumps[index * 4 + direction_index(direction)] {
+                EXIT => None,
+                target => Some(Position { x: target as i32 % 10, y: target as i32 / 10 }),
+            }
+        };
+
+        assert_eq!(jump(position, Compass::Up), Some(Position { x: 4, y: 1 }));
+        assert_eq!(jump(Position { x: 4, y: 1 }, Compass::Right), Some(Position { x: 8, y: 1 }));
+        assert_eq!(jump(Position { x: 8, y: 1 }, Compass::Down), Some(Position { x: 8, y: 6 }));
+        assert_eq!(jump(Position { x: 7, y: 7 }, Compass::Down), None);
+        assert_eq!(jump(Position { x: 0, y: 9 }, Compass::Left), None);
+        assert_eq!(jump(Position { x: 2, y: 4 }, Compass::Up), Some(Position { x: 2, y: 4 }));
+    }
+
+    #[test]
+    fn agrees_with_guard() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let board = DenseBoard::from(&grid);
+        let table = JumpTable::new(&board);
+
+        assert_eq!(table.loops(position, direction, None), guard_loops(&board, position, direction, None));
+
+        for y in 0..10 {
+            for x in 0..10 {
+                let obstacle = Position { x, y };
+                if obstacle == position || board.case(obstacle) == Some(Case::Obstacle()) {
+                    continue;
+                }
+
+                assert_eq!(
+                    table.loops(position, direction, Some(obstacle)),
+                    guard_loops(&board, position, direction, Some(obstacle)),
+                    "obstacle on {:?}",
+                    obstacle
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn loops_without_extra_obstacle() {
+        let (position, direction, grid) = parse_input(".#..\n...#\n#^..\n..#.").unwrap();
+        let board = DenseBoard::from(&grid);
+
+        assert!(guard_loops(&board, position, direction, None));
+        assert!(JumpTable::new(&board).loops(position, direction, None));
+    }
+
+    #[test]
+    fn looping_obstacles_test() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let board = DenseBoard::from(&grid);
+        let expected = looping_obstacles(&board, position, direction);
+
+        assert_eq!(expected.len(), 6);
+        assert_eq!(JumpTable::new(&board).looping_obstacles(&board, position, direction, 1), expected);
+    }
+}
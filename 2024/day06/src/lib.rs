@@ -0,0 +1,470 @@
+use common::{ParseError, Solution};
+use grid::error_at;
+
+pub mod board;
+pub mod cycle;
+pub mod export;
+pub mod jump;
+pub mod parallel;
+pub mod render;
+pub mod rules;
+pub mod sparse;
+pub mod squad;
+pub mod text;
+pub mod trap;
+
+pub use grid::{Compass, Grid, Position};
+
+pub use board::{Board, DenseBoard, History};
+pub use cycle::Cycle;
+pub use jump::JumpTable;
+pub use rules::{Rules, Turn};
+pub use sparse::SparseBoard;
+pub use squad::{Collision, Report, Squad};
+pub use text::MapText;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = (Position, Compass, Grid<Case>);
+    type Output = usize;
+
+    fn parse(input: &str) -> common::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one((starting_position, starting_direction, grid): &Self::Input) -> usize {
+        let mut guard = Guard::new(DenseBoard::from(grid), *starting_position, *starting_direction);
+        guard.patrol();
+
+        guard.history.len()
+    }
+
+    fn part_two((starting_position, starting_direction, grid): &Self::Input) -> usize {
+        part_two(grid, *starting_position, *starting_direction, parallel::default_threads())
+    }
+}
+
+/// Part two, spreading the search over `threads` workers.
+pub fn part_two(grid: &Grid<Case>, position: Position, direction: Compass, threads: usize) -> usize {
+    search_looping_obstacles(&DenseBoard::from(grid), position, direction, Rules::default(), threads).len()
+}
+
+/// The cells where a single new obstacle makes the guard loop under `rules`.
+/// The jump table only knows the default rules on a board without special
+/// tiles, anything else is a full patrol per candidate.
+pub fn search_looping_obstacles(board: &DenseBoard, position: Position, direction: Compass, rules: Rules, threads: usize) -> Vec<Position> {
+    if rules == Rules::default() && !board.has_tiles() {
+        JumpTable::new(board).looping_obstacles(board, position, direction, threads)
+    } else {
+        parallel::looping_obstacles(board, position, direction, rules, threads)
+    }
+}
+
+/// The cells where a single new obstacle may change the patrol: the free ones
+/// the guard walks on, except its starting position. An obstacle anywhere else
+/// is never met.
+pub fn obstacle_candidates<B: Board + Clone>(board: &B, position: Position, direction: Compass, rules: Rules) -> Vec<Position> {
+    let mut guard = Guard::with_rules(board.clone(), position, direction, rules);
+    guard.patrol();
+
+    let mut candidates: Vec<Position> = guard.history
+        .positions()
+        .into_iter()
+        .filter(|&candidate| candidate != position && board.case(candidate) == Some(Case::Free()))
+        .collect();
+
+    candidates.sort();
+    candidates
+}
+
+/// The cells where a single new obstacle makes the guard loop, sorted.
+pub fn looping_obstacles<B: Board + Clone>(board: &B, position: Position, direction: Compass) -> Vec<Position> {
+    obstacle_candidates(board, position, direction, Rules::default())
+        .into_iter()
+        .filter(|&candidate| {
+            let mut board = board.clone();
+            board.set_case(candidate, Case::Obstacle());
+
+            let mut guard = Guard::new(board, position, direction);
+            guard.patrol();
+
+            guard.looping
+        })
+        .collect()
+}
+
+pub fn count_looping_obstacles<B: Board + Clone>(board: &B, position: Position, direction: Compass) -> usize {
+    looping_obstacles(board, position, direction).len()
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Case {
+    Free(),
+    Obstacle(),
+    /// Only walkable in the given direction, an obstacle otherwise.
+    OneWay(Compass),
+    /// Sends the guard onto its twin, drawn with the same digit.
+    Teleporter(char, Position),
+    /// An obstacle the guard turns twice in front of.
+    DoubleWall(),
+}
+
+impl Case {
+    /// The character of the case on the map.
+    pub fn symbol(self) -> char {
+        match self {
+            Case::Free() => '.',
+            Case::Obstacle() => '#',
+            Case::OneWay(Compass::Up) => 'N',
+            Case::OneWay(Compass::Right) => 'E',
+            Case::OneWay(Compass::Down) => 'S',
+            Case::OneWay(Compass::Left) => 'W',
+            // Diagonal one-way tiles have no symbol of their own.
+            Case::OneWay(_) => '?',
+            Case::Teleporter(label, _) => label,
+            Case::DoubleWall() => '@',
+        }
+    }
+}
+
+/// What happened during one step of the guard.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum StepOutcome {
+    /// The guard walked to this case.
+    Moved(Position),
+    /// The guard bumped into an obstacle and now faces this direction.
+    Turned(Compass),
+    /// The guard left the map.
+    Exited,
+    /// The guard is back in a state it already had.
+    LoopDetected,
+}
+
+impl StepOutcome {
+    /// Whether the patrol is over after this step.
+    pub fn is_final(self) -> bool {
+        matches!(self, StepOutcome::Exited | StepOutcome::LoopDetected)
+    }
+}
+
+pub struct Guard<B: Board = Grid<Case>> {
+    pub direction: Compass,
+    pub position: Position,
+    pub history: B::History,
+    pub grid: B,
+    pub walking: bool,
+    pub looping: bool,
+    pub rules: Rules,
+}
+
+impl<B: Board> Guard<B> {
+    pub fn new(grid: B, position: Position, direction: Compass) -> Guard<B> {
+        Guard::with_rules(grid, position, direction, Rules::default())
+    }
+
+    pub fn with_rules(grid: B, position: Position, direction: Compass, rules: Rules) -> Guard<B> {
+        let mut guard = Guard{
+            position,
+            direction,
+            history: grid.history(),
+            grid,
+            walking: true,
+            looping: false,
+            rules,
+        };
+
+        guard.history.visit(position, direction);
+
+        guard
+    }
+
+    /// Walk until the guard leaves the map or starts looping.
+    pub fn patrol(&mut self) {
+        for _ in self.steps() {}
+    }
+
+    /// Walk at most `max_steps` steps, returning whether the patrol is over.
+    /// Needed on boards the guard may never leave.
+    pub fn patrol_for(&mut self, max_steps: usize) -> bool {
+        self.steps().take(max_steps).for_each(drop);
+        !self.walking || self.looping
+    }
+
+    /// The remaining steps of the patrol, the last one being `Exited` or
+    /// `LoopDetected`.
+    pub fn steps(&mut self) -> Steps<'_, B> {
+        Steps { guard: self }
+    }
+
+    /// Take one step. Once the patrol is over, keep returning how it ended.
+    pub fn walk(&mut self) -> StepOutcome {
+        if self.looping {
+            return StepOutcome::LoopDetected;
+        }
+
+        if !self.walking {
+            return StepOutcome::Exited;
+        }
+
+        let Some((position, direction)) = self.rules.step(&self.grid, self.position, self.direction) else {
+            self.walking = false;
+            return StepOutcome::Exited;
+        };
+
+        // Turning also records the new direction, so turning around in place
+        // forever is a loop too.
+        if !self.history.visit(position, direction) {
+            self.looping = true;
+            return StepOutcome::LoopDetected;
+        }
+
+        let outcome = if position == self.position {
+            StepOutcome::Turned(direction)
+        } else {
+            StepOutcome::Moved(position)
+        };
+
+        self.position = position;
+        self.direction = direction;
+
+        outcome
+    }
+}
+
+/// Iterator over the steps of a guard, see `Guard::steps`.
+pub struct Steps<'a, B: Board> {
+    guard: &'a mut Guard<B>,
+}
+
+impl<B: Board> Iterator for Steps<'_, B> {
+    type Item = StepOutcome;
+
+    fn next(&mut self) -> Option<StepOutcome> {
+        if !self.guard.walking || self.guard.looping {
+            return None;
+        }
+
+        Some(self.guard.walk())
+    }
+}
+
+/// Parse a map with a single guard.
+pub fn parse_input(input: &str) -> common::Result<(Position, Compass, Grid<Case>)> {
+    let (guards, grid) = parse_guards(input)?;
+
+    if let Some(&(position, _)) = guards.get(1) {
+        return Err(error_at(input, position, "a second guard is on the map, expected only one"));
+    }
+
+    let (position, direction) = guards[0];
+    Ok((position, direction, grid))
+}
+
+/// Starting position and direction of each guard.
+pub type Guards = Vec<(Position, Compass)>;
+
+/// Parse a map with any number of guards, at least one, in reading order.
+pub fn parse_guards(input: &str) -> common::Result<(Guards, Grid<Case>)> {
+    let mut guards = Vec::new();
+    let mut teleporters: Vec<(char, Position)> = Vec::new();
+
+    let mut grid = Grid::parse(input, |position, char| {
+        let case = match char {
+            '#' | 'O' => Case::Obstacle(),
+            '.' | 'X' => Case::Free(),
+            '^' | '>' | 'v' | '<' => {
+                let direction = match char {
+                    '^' => Compass::Up,
+                    '>' => Compass::Right,
+                    'v' => Compass::Down,
+                    _ => Compass::Left,
+                };
+                guards.push((position, direction));
+                Case::Free()
+            }
+            'N' => Case::OneWay(Compass::Up),
+            'E' => Case::OneWay(Compass::Right),
+            'S' => Case::OneWay(Compass::Down),
+            'W' => Case::OneWay(Compass::Left),
+            '@' => Case::DoubleWall(),
+            '0'..='9' => {
+                teleporters.push((char, position));
+                Case::Free()
+            }
+            other => {
+                return Err(format!(
+                    "unexpected `{}`, expected one of `.`, `#`, `X`, `O`, `@`, `^`, `>`, `v`, `<`, `N`, `E`, `S`, `W` or a digit",
+                    other
+                ))
+            }
+        };
+
+        Ok(case)
+    })?;
+
+    // Teleporters come in pairs of the same digit.
+    for &(label, position) in &teleporters {
+        let twins: Vec<Position> = teleporters
+            .iter()
+            .filter(|&&(other, other_position)| other == label && other_position != position)
+            .map(|&(_, other_position)| other_position)
+            .collect();
+
+        if twins.len() != 1 {
+            let message = format!("teleporter `{}` appears {} times, expected exactly 2", label, twins.len() + 1);
+            return Err(error_at(input, position, message));
+        }
+
+        grid[position] = Case::Teleporter(label, twins[0]);
+    }
+
+    if guards.is_empty() {
+        let input = input.trim_end();
+        let line = input.lines().last().unwrap_or("");
+        let line_number = input.lines().count().max(1);
+        return Err(ParseError::end_of_line(line_number, line, "no guard found on the map"));
+    }
+
+    Ok((guards, grid))
+}
+
+/// The map of the puzzle example.
+#[cfg(test)]
+pub(crate) const SAMPLE: &str = "....#.....\n\
+                                 .........#\n\
+                                 ..........\n\
+                                 ..#.......\n\
+                                 .......#..\n\
+                                 ..........\n\
+                                 .#..^.....\n\
+                                 ........#.\n\
+                                 #.........\n\
+                                 ......#...";
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn hash_map(grid: &Grid<Case>) -> HashMap<Position, Case> {
+        grid.iter().map(|(position, &case)| (position, case)).collect()
+    }
+
+     #[test]
+     fn parse_input_test() {
+         let expected_grid = Grid::from_cells(
+             5,
+             vec![Case::Free(), Case::Obstacle(), Case::Free(), Case::Obstacle(), Case::Free()],
+         );
+
+         let (starting_position, starting_direction, grid) = parse_input(".#^#.").unwrap();
+         assert_eq!(starting_position, Position{x: 2, y: 0});
+         assert_eq!(starting_direction, Compass::Up);
+         assert_eq!(grid, expected_grid);
+
+         let (_, _, grid) = parse_input("1N@\n^.1").unwrap();
+         assert_eq!(grid[Position { x: 0, y: 0 }], Case::Teleporter('1', Position { x: 2, y: 1 }));
+         assert_eq!(grid[Position { x: 2, y: 1 }], Case::Teleporter('1', Position { x: 0, y: 0 }));
+         assert_eq!(grid[Position { x: 1, y: 0 }], Case::OneWay(Compass::Up));
+         assert_eq!(grid[Position { x: 2, y: 0 }], Case::DoubleWall());
+     }
+
+    #[test]
+    fn parse_guards_test() {
+        let (guards, grid) = parse_guards(".v.\n>.<\n.^#").unwrap();
+
+        assert_eq!(
+            guards,
+            vec![
+                (Position { x: 1, y: 0 }, Compass::Down),
+                (Position { x: 0, y: 1 }, Compass::Right),
+                (Position { x: 2, y: 1 }, Compass::Left),
+                (Position { x: 1, y: 2 }, Compass::Up),
+            ]
+        );
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[Position { x: 1, y: 0 }], Case::Free());
+    }
+
+    #[test]
+    fn parse_input_errors() {
+        let error = parse_input("..#\r\n.^x\r\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.width), (2, 3, 1));
+
+        let error = parse_input("..#\n...").unwrap_err();
+        assert_eq!(error.message, "no guard found on the map");
+
+        let error = parse_input("1.^\n..2\n1.1").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.message, "teleporter `1` appears 3 times, expected exactly 2");
+
+        let error = parse_input("^..\n.<.").unwrap_err();
+        assert_eq!((error.line, error.column, error.width), (2, 2, 1));
+        assert_eq!(error.message, "a second guard is on the map, expected only one");
+    }
+
+    #[test]
+    fn test_patrol() {
+        let (starting_position, starting_direction, grid) = parse_input(SAMPLE).unwrap();
+        let mut guard = Guard::new(grid.clone(), starting_position, starting_direction);
+        guard.patrol();
+
+        assert_eq!(guard.history.len(), 41);
+
+        let mut guard = Guard::new(hash_map(&grid), starting_position, starting_direction);
+        guard.patrol();
+
+        assert_eq!(guard.history.len(), 41);
+
+        let mut guard = Guard::new(DenseBoard::from(&grid), starting_position, starting_direction);
+        guard.patrol();
+
+        assert_eq!(guard.history.len(), 41);
+    }
+
+    #[test]
+    fn test_steps() {
+        let (position, direction, grid) = parse_input("#.\n^.").unwrap();
+        let mut guard = Guard::new(grid, position, direction);
+
+        let steps: Vec<StepOutcome> = guard.steps().collect();
+        assert_eq!(
+            steps,
+            vec![StepOutcome::Turned(Compass::Right), StepOutcome::Moved(Position { x: 1, y: 1 }), StepOutcome::Exited]
+        );
+        assert!(!guard.walking);
+        assert_eq!(guard.walk(), StepOutcome::Exited);
+        assert_eq!(guard.steps().next(), None);
+
+        let (position, direction, grid) = parse_input("###\n#^#\n###").unwrap();
+        let mut guard = Guard::new(grid, position, direction);
+
+        let steps: Vec<StepOutcome> = guard.steps().collect();
+        assert_eq!(
+            steps,
+            vec![
+                StepOutcome::Turned(Compass::Right),
+                StepOutcome::Turned(Compass::Down),
+                StepOutcome::Turned(Compass::Left),
+                StepOutcome::LoopDetected,
+            ]
+        );
+        assert!(steps.last().unwrap().is_final());
+        assert!(guard.looping);
+    }
+
+    #[test]
+    fn test_looping_obstacles() {
+        let (starting_position, starting_direction, grid) = parse_input(SAMPLE).unwrap();
+
+        assert_eq!(count_looping_obstacles(&grid, starting_position, starting_direction), 6);
+        assert_eq!(count_looping_obstacles(&hash_map(&grid), starting_position, starting_direction), 6);
+
+        let board = DenseBoard::from(&grid);
+        assert_eq!(count_looping_obstacles(&board, starting_position, starting_direction), 6);
+    }
+}
@@ -1,209 +1,287 @@
-use std::collections::HashMap;
-use std::fs::read_to_string;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use common::Solution;
+use day06::sparse::{self, SparseBoard};
+use day06::{cycle, export, parallel, render, trap, Board, Case, Day06, DenseBoard, Compass, Guard, History, Position, Rules, Turn};
+
+const USAGE: &str = "Usage: day06 [PATH|-] [--threads <N>] [--animate] [--frames <DIR>] [--every <N>] [--delay <MS>] [--explain]
+             [--turn <left|right|reverse>] [--diagonal] [--json <FILE>] [--csv <FILE>]
+             [--trap <MAX>] [--coordinates [--max-steps <N>]]
+
+  --animate       play the patrol in the terminal
+  --frames <DIR>  write the frames of the patrol to text files in DIR
+  --every <N>     only draw one frame every N steps (default: 1)
+  --delay <MS>    time between two frames of the animation (default: 50)
+  --explain       describe the loop made by each obstacle of part two
+  --turn <WAY>    which way the guard turns in front of obstacles (default: right)
+  --diagonal      turn by an eighth, so the guard also walks diagonally
+  --json <FILE>   write the step log of the patrol to FILE as JSON
+  --csv <FILE>    write the step log of the patrol to FILE as CSV
+  --trap <MAX>    find the fewest obstacles, up to MAX, keeping the guard on the map
+  --coordinates   read the input as a list of obstacles, see the README
+  --max-steps <N> give up on a patrol without bounds after N steps (default: 1000000)";
 
-fn main() {
-    let input = read_to_string("./inputs/day06.txt").unwrap();
-
-    let (starting_position, starting_direction, grid) = parse_input(&input);
-    let mut guard = Guard::new(grid, starting_position, starting_direction);
-
-    while guard.walking && !guard.looping {
-        guard.walk();
-    }
-
-    println!("{}", guard.history.len());
+#[derive(Debug, PartialEq)]
+struct Options {
+    path: Option<String>,
+    threads: usize,
+    animate: bool,
+    frames: Option<PathBuf>,
+    every: usize,
+    delay: Duration,
+    explain: bool,
+    rules: Rules,
+    json: Option<PathBuf>,
+    csv: Option<PathBuf>,
+    trap: Option<usize>,
+    coordinates: bool,
+    max_steps: usize,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right
-}
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = parse_args(&args).unwrap_or_else(|message| {
+        eprintln!("{}\n\n{}", message, USAGE);
+        process::exit(2);
+    });
+
+    if options.coordinates {
+        run_coordinates(&options);
+        return;
+    }
 
-#[derive(Debug, PartialEq)]
-enum Case {
-    Free(),
-    Obstacle(),
-}
+    let (position, direction, grid) = common::load::<Day06>(options.path.as_deref());
+    let board = DenseBoard::from(&grid);
+    let guard = || Guard::with_rules(board.clone(), position, direction, options.rules);
 
-#[derive(Hash, Debug, PartialEq, Eq, Copy, Clone)]
-struct Position {
-    x: i32,
-    y: i32,
-}
+    if options.animate {
+        let mut guard = guard();
+        exit_on_error(render::animate(&mut guard, &mut io::stdout(), options.every, options.delay));
+    }
 
-struct Guard {
-    direction: Direction,
-    position: Position,
-    history: HashMap<Position, Vec<Direction>>,
-    grid: HashMap<Position, Case>,
-    walking: bool,
-    looping: bool,
-}
+    if let Some(directory) = &options.frames {
+        let mut guard = guard();
+        let count = exit_on_error(render::export_frames(&mut guard, directory, options.every));
+        println!("{} frames written to {}", count, directory.display());
+    }
 
-impl Guard {
-    fn new(grid: HashMap<Position, Case>, position: Position, direction: Direction) -> Guard {
-        let mut guard = Guard{
-            position,
-            direction,
-            history: HashMap::new(),
-            grid,
-            walking: true,
-            looping: false
-        };
+    if options.json.is_some() || options.csv.is_some() {
+        let steps = export::log(&mut guard());
 
-        guard.history.entry(position).or_insert(Vec::new()).push(direction);
+        if let Some(path) = &options.json {
+            exit_on_error(fs::write(path, export::to_json(&steps)));
+        }
 
-        guard
+        if let Some(path) = &options.csv {
+            exit_on_error(fs::write(path, export::to_csv(&steps)));
+        }
     }
 
-    fn can_walk_to(&mut self, position: Position) -> bool {
-        if let Some(case) = self.grid.get(&position) {
-            *case == Case::Free()
-        } else {
-            self.walking = false;
-            false
-        }
+    if options.explain {
+        explain(&board, position, direction, options.rules, options.threads);
     }
 
-    fn walk(&mut self) {
-        let next_position =
-            match self.direction {
-                Direction::Up => self.move_up(),
-                Direction::Down => self.move_down(),
-                Direction::Left => self.move_left(),
-                Direction::Right => self.move_right(),
-            };
-
-        if self.can_walk_to(next_position) {
-            let case_history = self.history.entry(next_position).or_insert(Vec::new());
-
-            if case_history.contains(&self.direction) {
-                self.looping = true;
-            } else {
-               case_history.push(self.direction);
-               self.position = next_position;
+    let mut guard = guard();
+    guard.patrol();
+
+    println!("Part one: {}", guard.history.len());
+    println!("Part two: {}", day06::search_looping_obstacles(&board, position, direction, options.rules, options.threads).len());
+
+    if let Some(max) = options.trap {
+        match trap::min_obstacles(&board, position, direction, options.rules, max) {
+            Some(obstacles) => {
+                let obstacles: Vec<String> = obstacles.iter().map(|obstacle| format!("({}, {})", obstacle.x, obstacle.y)).collect();
+                println!("Fewest obstacles trapping the guard: {}, at {}", obstacles.len(), obstacles.join(" "));
             }
-        } else {
-            self.rotate();
+            None => println!("No way to trap the guard with up to {} obstacles", max),
         }
     }
+}
 
-    fn move_up(&self) -> Position {
-        Position{x: self.position.x, y: self.position.y - 1}
+/// Solve a coordinate list on a sparse board. Without bounds, part one gives
+/// up after `--max-steps` and part two is not possible.
+fn run_coordinates(options: &Options) {
+    let (position, direction, board): (Position, Compass, SparseBoard) =
+        common::load_with(Day06::DAY, options.path.as_deref(), sparse::parse_coordinates);
+    let mut guard = Guard::with_rules(board.clone(), position, direction, options.rules);
+
+    if board.bounds().is_some() {
+        guard.patrol();
+    } else if !guard.patrol_for(options.max_steps) {
+        println!("Part one: the guard is still walking after {} steps", options.max_steps);
+        return;
     }
 
-    fn move_down(&self) -> Position {
-        Position{x: self.position.x, y: self.position.y + 1}
-    }
+    println!("Part one: {}", guard.history.len());
 
-    fn move_left(&self) -> Position {
-        Position{x: self.position.x - 1, y: self.position.y}
+    match board.bounds() {
+        Some(_) => println!("Part two: {}", parallel::looping_obstacles(&board, position, direction, options.rules, options.threads).len()),
+        None => println!("Part two: needs the size of the map"),
     }
+}
 
-    fn move_right(&self) -> Position {
-        Position{x: self.position.x + 1, y: self.position.y}
+fn explain(board: &DenseBoard, position: Position, direction: Compass, rules: Rules, threads: usize) {
+    match cycle::analyse_with_rules(board, position, direction, rules) {
+        Some(cycle) => println!("Patrol: {}", cycle),
+        None => println!("Patrol: the guard leaves the map"),
     }
 
-    fn rotate(&mut self) {
-        match self.direction {
-            Direction::Up => self.direction = Direction::Right,
-            Direction::Down => self.direction = Direction::Left,
-            Direction::Left => self.direction = Direction::Up,
-            Direction::Right => self.direction = Direction::Down,
+    for obstacle in day06::search_looping_obstacles(board, position, direction, rules, threads) {
+        let mut board = board.clone();
+        board.set_case(obstacle, Case::Obstacle());
+
+        if let Some(cycle) = cycle::analyse_with_rules(&board, position, direction, rules) {
+            println!("Obstacle at ({}, {}): {}", obstacle.x, obstacle.y, cycle);
         }
     }
 }
 
-fn parse_input(input: &str) -> (Position, Direction, HashMap<Position, Case>) {
-    let mut grid = HashMap::new();
-    let mut starting_position = Position{x: 0, y: 0};
-    let mut starting_direction = Direction::Up;
-
-    for (row, line) in input.lines().enumerate() {
-        for (col, char) in line.chars().enumerate() {
-            let position = Position{x: col as i32, y: row as i32};
-
-            match char {
-                '#' => grid.entry(position).or_insert(Case::Obstacle()),
-                '^' => {
-                  starting_position = position;
-                  starting_direction = Direction::Up;
-                  grid.entry(position).or_insert(Case::Free())
-                },
-                '>' => {
-                  starting_position = position;
-                  starting_direction = Direction::Right;
-                  grid.entry(position).or_insert(Case::Free())
-                },
-                'v' => {
-                  starting_position = position;
-                  starting_direction = Direction::Down;
-                  grid.entry(position).or_insert(Case::Free())
-                },
-                '<' => {
-                  starting_position = position;
-                  starting_direction = Direction::Left;
-                  grid.entry(position).or_insert(Case::Free())
-                },
-                _other => grid.entry(position).or_insert(Case::Free()),
-            };
+fn exit_on_error<T>(result: io::Result<T>) -> T {
+    result.unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    })
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        path: None,
+        threads: parallel::default_threads(),
+        animate: false,
+        frames: None,
+        every: 1,
+        delay: Duration::from_millis(50),
+        explain: false,
+        rules: Rules::default(),
+        json: None,
+        csv: None,
+        trap: None,
+        coordinates: false,
+        max_steps: 1_000_000,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threads" | "-j" => {
+                options.threads = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|&threads| threads > 0)
+                    .ok_or("--threads expects a positive number")?;
+            }
+            "--animate" => options.animate = true,
+            "--explain" => options.explain = true,
+            "--diagonal" => options.rules.diagonal = true,
+            "--json" => {
+                let path = args.next().ok_or("Missing file for --json")?;
+                options.json = Some(PathBuf::from(path));
+            }
+            "--coordinates" => options.coordinates = true,
+            "--max-steps" => {
+                options.max_steps = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or("--max-steps expects a number of steps")?;
+            }
+            "--trap" => {
+                let max = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or("--trap expects a number of obstacles")?;
+                options.trap = Some(max);
+            }
+            "--csv" => {
+                let path = args.next().ok_or("Missing file for --csv")?;
+                options.csv = Some(PathBuf::from(path));
+            }
+            "--turn" => {
+                options.rules.turn = match args.next().map(String::as_str) {
+                    Some("left") => Turn::Left,
+                    Some("right") => Turn::Right,
+                    Some("reverse") => Turn::Reverse,
+                    _ => return Err("--turn expects `left`, `right` or `reverse`".to_string()),
+                };
+            }
+            "--frames" => {
+                let directory = args.next().ok_or("Missing directory for --frames")?;
+                options.frames = Some(PathBuf::from(directory));
+            }
+            "--every" => {
+                options.every = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|&every| every > 0)
+                    .ok_or("--every expects a positive number")?;
+            }
+            "--delay" => {
+                let delay = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or("--delay expects a number of milliseconds")?;
+                options.delay = Duration::from_millis(delay);
+            }
+            _ if options.path.is_none() => options.path = Some(arg.clone()),
+            other => return Err(format!("Unknown argument `{}`", other)),
         }
     }
 
-    (starting_position, starting_direction, grid)
+    Ok(options)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "....#.....\n\
-                         .........#\n\
-                         ..........\n\
-                         ..#.......\n\
-                         .......#..\n\
-                         ..........\n\
-                         .#..^.....\n\
-                         ........#.\n\
-                         #.........\n\
-                         ......#...";
-
-     #[test]
-     fn parse_input_test() {
-         let mut expected_grid: HashMap<Position, Case> = HashMap::new();
-         expected_grid.insert(Position { x: 0, y: 0 }, Case::Free());
-         expected_grid.insert(Position { x: 1, y: 0 }, Case::Obstacle());
-         expected_grid.insert(Position { x: 2, y: 0 }, Case::Free());
-         expected_grid.insert(Position { x: 3, y: 0 }, Case::Obstacle());
-         expected_grid.insert(Position { x: 4, y: 0 }, Case::Free());
-
-         let (starting_position, starting_direction, grid) = parse_input(&".#^#.");
-         assert_eq!(starting_position, Position{x: 2, y: 0});
-         assert_eq!(starting_direction, Direction::Up);
-         assert_eq!(grid, expected_grid);
-     }
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
 
     #[test]
-    fn test_patrol() {
-        let (starting_position, starting_direction, grid) = parse_input(INPUT);
-        let mut guard = Guard::new(grid, starting_position, starting_direction);
-        let mut grid = [['.'; 10]; 10];
-
-        for (pos, case) in &guard.grid {
-            if *case == Case::Free() {
-                grid[pos.y as usize][pos.x as usize] = '.';
-            } else {
-                grid[pos.y as usize][pos.x as usize] = '#';
-            }
-        }
-
-        while guard.walking && !guard.looping {
-            guard.walk();
-        }
+    fn parse_args_test() {
+        assert_eq!(parse_args(&args("")).unwrap().path, None);
+        assert_eq!(parse_args(&args("input.txt --threads 3")).unwrap().path, Some("input.txt".to_string()));
+        assert_eq!(parse_args(&args("input.txt --threads 3")).unwrap().threads, 3);
+        assert_eq!(parse_args(&args("-j 2 -")).unwrap().path, Some("-".to_string()));
+
+        let options = parse_args(&args("--animate --every 10 --delay 5 --frames out")).unwrap();
+        assert!(options.animate);
+        assert_eq!(options.every, 10);
+        assert_eq!(options.delay, Duration::from_millis(5));
+        assert_eq!(options.frames, Some(PathBuf::from("out")));
+        assert!(!options.explain);
+        assert!(parse_args(&args("--explain")).unwrap().explain);
+        assert_eq!(parse_args(&args("")).unwrap().rules, Rules::default());
+        assert_eq!(
+            parse_args(&args("--turn left --diagonal")).unwrap().rules,
+            Rules { turn: Turn::Left, diagonal: true }
+        );
+
+        let options = parse_args(&args("--json steps.json --csv steps.csv")).unwrap();
+        assert_eq!(options.json, Some(PathBuf::from("steps.json")));
+        assert_eq!(options.csv, Some(PathBuf::from("steps.csv")));
+        assert_eq!(parse_args(&args("--trap 3")).unwrap().trap, Some(3));
+
+        let options = parse_args(&args("list.txt --coordinates --max-steps 50")).unwrap();
+        assert!(options.coordinates);
+        assert_eq!(options.max_steps, 50);
+    }
 
-        assert_eq!(guard.history.len(), 41);
+    #[test]
+    fn parse_args_errors() {
+        assert!(parse_args(&args("--threads 0")).is_err());
+        assert!(parse_args(&args("--threads")).is_err());
+        assert!(parse_args(&args("a.txt b.txt")).is_err());
+        assert!(parse_args(&args("--every 0")).is_err());
+        assert!(parse_args(&args("--frames")).is_err());
+        assert!(parse_args(&args("--delay soon")).is_err());
+        assert!(parse_args(&args("--turn around")).is_err());
+        assert!(parse_args(&args("--json")).is_err());
+        assert!(parse_args(&args("--trap many")).is_err());
     }
 }
@@ -0,0 +1,132 @@
+//! Run the loop searches on several threads. Every candidate is an independent
+//! patrol, so they are simply split between workers, each one owning its own
+//! state (usually a copy of the board). Results keep the order of the
+//! candidates, whatever the number of threads.
+//!
+//! Workers are plain `std` threads, or a rayon pool with the `rayon` feature.
+
+use std::thread;
+
+use crate::{obstacle_candidates, Board, Case, Compass, Guard, Position, Rules};
+
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1)
+}
+
+/// Keep the `candidates` passing `check`. Each worker creates its state with
+/// `init` and gives it to every `check` it runs.
+#[cfg(not(feature = "rayon"))]
+pub fn filter<T, I, F>(candidates: &[Position], threads: usize, init: I, check: F) -> Vec<Position>
+where
+    I: Fn() -> T + Sync,
+    F: Fn(&mut T, Position) -> bool + Sync,
+{
+    let chunk_size = candidates.len().div_ceil(threads.max(1)).max(1);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = candidates
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let (init, check) = (&init, &check);
+
+                scope.spawn(move || {
+                    let mut state = init();
+
+                    chunk.iter()
+                        .copied()
+                        .filter(|&candidate| check(&mut state, candidate))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("a loop search worker panicked"))
+            .collect()
+    })
+}
+
+/// Keep the `candidates` passing `check`. Each worker creates its state with
+/// `init` and gives it to every `check` it runs.
+#[cfg(feature = "rayon")]
+pub fn filter<T, I, F>(candidates: &[Position], threads: usize, init: I, check: F) -> Vec<Position>
+where
+    I: Fn() -> T + Sync,
+    F: Fn(&mut T, Position) -> bool + Sync,
+{
+    use rayon::prelude::*;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.max(1))
+        .build()
+        .expect("cannot start the rayon thread pool");
+
+    pool.install(|| {
+        candidates
+            .par_iter()
+            .copied()
+            .map_init(&init, |state, candidate| (candidate, check(state, candidate)))
+            .filter(|(_, keep)| *keep)
+            .map(|(candidate, _)| candidate)
+            .collect()
+    })
+}
+
+/// Same as `looping_obstacles` under `rules`, over `threads` workers. Each
+/// worker copies the board once, then adds and removes the new obstacle for
+/// each candidate.
+pub fn looping_obstacles<B>(board: &B, position: Position, direction: Compass, rules: Rules, threads: usize) -> Vec<Position>
+where
+    B: Board + Clone + Sync,
+{
+    let candidates = obstacle_candidates(board, position, direction, rules);
+
+    filter(&candidates, threads, || Some(board.clone()), |state: &mut Option<B>, candidate| {
+        let mut board = state.take().expect("the worker board is always given back");
+        let previous = board.case(candidate).unwrap_or(Case::Free());
+        board.set_case(candidate, Case::Obstacle());
+
+        let mut guard = Guard::with_rules(board, position, direction, rules);
+        guard.patrol();
+
+        let looping = guard.looping;
+        let mut board = guard.grid;
+        board.set_case(candidate, previous);
+        *state = Some(board);
+
+        looping
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, DenseBoard, JumpTable, SAMPLE};
+
+    #[test]
+    fn filter_keeps_order() {
+        let candidates: Vec<Position> = (0..50).map(|x| Position { x, y: 0 }).collect();
+        let expected: Vec<Position> = candidates.iter().copied().filter(|p| p.x % 3 == 0).collect();
+
+        for threads in 1..=8 {
+            assert_eq!(filter(&candidates, threads, || (), |_, p| p.x % 3 == 0), expected);
+        }
+
+        assert_eq!(filter(&[], 4, || (), |_, _| true), vec![]);
+    }
+
+    #[test]
+    fn same_as_single_threaded() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let board = DenseBoard::from(&grid);
+        let expected = crate::looping_obstacles(&grid, position, direction);
+        assert_eq!(expected.len(), 6);
+
+        for threads in 1..=4 {
+            assert_eq!(looping_obstacles(&grid, position, direction, Rules::default(), threads), expected);
+            assert_eq!(looping_obstacles(&board, position, direction, Rules::default(), threads), expected);
+            assert_eq!(JumpTable::new(&board).looping_obstacles(&board, position, direction, threads), expected);
+        }
+    }
+}
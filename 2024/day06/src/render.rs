@@ -0,0 +1,203 @@
+//! Draw the guard patrol: obstacles as `#`, the guard as `^`, `>`, `v` or
+//! `<`, and the visited cases as `|`, `-` or `+` depending on the directions
+//! the guard had on them, `x` when it only crossed them diagonally. Special
+//! tiles keep their own symbol.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::{Board, Case, Compass, Guard, History, Position};
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+
+pub fn render<B: Board>(guard: &Guard<B>) -> String {
+    let (width, height) = guard.grid.size();
+    let mut frame = String::with_capacity((width + 1) * height);
+
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            frame.push(cell(guard, Position { x, y }));
+        }
+        frame.push('\n');
+    }
+
+    frame
+}
+
+fn cell<B: Board>(guard: &Guard<B>, position: Position) -> char {
+    if guard.walking && position == guard.position {
+        return glyph(guard.direction);
+    }
+
+    match guard.grid.case(position) {
+        Some(Case::Free()) => {
+            let vertical = guard.history.contains(position, Compass::Up)
+                || guard.history.contains(position, Compass::Down);
+            let horizontal = guard.history.contains(position, Compass::Left)
+                || guard.history.contains(position, Compass::Right);
+            let diagonal = Compass::ALL
+                .iter()
+                .any(|&direction| direction.is_diagonal() && guard.history.contains(position, direction));
+
+            match (vertical, horizontal, diagonal) {
+                (true, true, _) => '+',
+                (true, false, _) => '|',
+                (false, true, _) => '-',
+                (false, false, true) => 'x',
+                (false, false, false) => '.',
+            }
+        }
+        Some(case) => case.symbol(),
+        None => ' ',
+    }
+}
+
+pub fn glyph(direction: Compass) -> char {
+    match direction {
+        Compass::Up => '^',
+        Compass::Right => '>',
+        Compass::Down => 'v',
+        Compass::Left => '<',
+        Compass::UpRight | Compass::DownLeft => '/',
+        Compass::DownRight | Compass::UpLeft => '\\',
+    }
+}
+
+/// Walk the guard until the end of its patrol, calling `on_frame` with the
+/// frame number and the drawing every `every` steps, and on the last step.
+fn frames<B: Board>(guard: &mut Guard<B>, every: usize, mut on_frame: impl FnMut(usize, &str) -> io::Result<()>) -> io::Result<usize> {
+    let every = every.max(1);
+    let mut step = 0;
+    let mut count = 1;
+
+    on_frame(0, &render(guard))?;
+
+    loop {
+        let outcome = guard.walk();
+        step += 1;
+
+        if step % every == 0 || outcome.is_final() {
+            on_frame(count, &render(guard))?;
+            count += 1;
+        }
+
+        if outcome.is_final() {
+            return Ok(count);
+        }
+    }
+}
+
+/// Play the patrol in the terminal, redrawing the map in place.
+pub fn animate<B: Board, W: Write>(guard: &mut Guard<B>, out: &mut W, every: usize, delay: Duration) -> io::Result<()> {
+    write!(out, "{}", CLEAR_SCREEN)?;
+
+    frames(guard, every, |_, frame| {
+        write!(out, "{}{}", CURSOR_HOME, frame)?;
+        out.flush()?;
+        thread::sleep(delay);
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+/// Write the frames of the patrol to `directory` as `frame_00000.txt`,
+/// `frame_00001.txt`... Returns the number of written frames.
+pub fn export_frames<B: Board>(guard: &mut Guard<B>, directory: &Path, every: usize) -> io::Result<usize> {
+    fs::create_dir_all(directory)?;
+
+    frames(guard, every, |number, frame| {
+        fs::write(directory.join(format!("frame_{:05}.txt", number)), frame)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, DenseBoard, SAMPLE};
+
+    #[test]
+    fn render_start() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let guard = Guard::new(grid, position, direction);
+
+        assert_eq!(render(&guard), format!("{}\n", SAMPLE));
+
+        let (position, direction, grid) = parse_input("1N@.\n>.1W").unwrap();
+        let guard = Guard::new(grid, position, direction);
+
+        assert_eq!(render(&guard), "1N@.\n>.1W\n");
+    }
+
+    #[test]
+    fn render_patrol() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let mut guard = Guard::new(DenseBoard::from(&grid), position, direction);
+
+        for _ in 0..8 {
+            guard.walk();
+        }
+
+        assert_eq!(
+            render(&guard),
+            "....#.....\n\
+             ....+->..#\n\
+             ....|.....\n\
+             ..#.|.....\n\
+             ....|..#..\n\
+             ....|.....\n\
+             .#..|.....\n\
+             ........#.\n\
+             #.........\n\
+             ......#...\n"
+        );
+
+        guard.patrol();
+
+        assert_eq!(
+            render(&guard),
+            "....#.....\n\
+             ....+---+#\n\
+             ....|...|.\n\
+             ..#.|...|.\n\
+             ..+-+-+#|.\n\
+             ..|.|.|.|.\n\
+             .#+-+-+-+.\n\
+             .+----++#.\n\
+             #+----+|..\n\
+             ......#|..\n"
+        );
+    }
+
+    #[test]
+    fn export_frames_test() {
+        let (position, direction, grid) = parse_input("#..\n..^").unwrap();
+        let mut guard = Guard::new(grid, position, direction);
+        let directory = std::env::temp_dir().join(format!("day06_frames_{}", std::process::id()));
+
+        let count = export_frames(&mut guard, &directory, 1).unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(fs::read_to_string(directory.join("frame_00000.txt")).unwrap(), "#..\n..^\n");
+        assert_eq!(fs::read_to_string(directory.join("frame_00001.txt")).unwrap(), "#.^\n..|\n");
+        assert_eq!(fs::read_to_string(directory.join("frame_00002.txt")).unwrap(), "#.|\n..|\n");
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn animate_test() {
+        let (position, direction, grid) = parse_input("#..\n..^").unwrap();
+        let mut guard = Guard::new(grid, position, direction);
+        let mut out = Vec::new();
+
+        animate(&mut guard, &mut out, 2, Duration::ZERO).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out, "\x1b[2J\x1b[H#..\n..^\n\x1b[H#.|\n..|\n");
+    }
+}
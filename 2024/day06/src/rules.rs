@@ -0,0 +1,114 @@
+//! Variants of the patrol: which way the guard turns, whether it also moves
+//! diagonally, and what it does on the special tiles of the map.
+
+use crate::{Board, Case, Compass, Position};
+
+/// Which way the guard turns in front of an obstacle.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum Turn {
+    Left,
+    #[default]
+    Right,
+    Reverse,
+}
+
+/// The default rules are the puzzle ones: a quarter turn to the right in front
+/// of an obstacle, and no diagonal moves.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct Rules {
+    pub turn: Turn,
+    /// Turn by an eighth instead of a quarter, so the guard also walks along
+    /// the diagonals.
+    pub diagonal: bool,
+}
+
+impl Rules {
+    pub fn turn(&self, direction: Compass) -> Compass {
+        let eighths = if self.diagonal { 1 } else { 2 };
+
+        match self.turn {
+            Turn::Left => direction.rotate(-eighths),
+            Turn::Right => direction.rotate(eighths),
+            Turn::Reverse => direction.opposite(),
+        }
+    }
+
+    /// Where the guard stands and which way it faces after one step from
+    /// `position`, `None` when it leaves the map, or the range of `i32` on an
+    /// infinite board.
+    pub fn step<B: Board>(&self, board: &B, position: Position, direction: Compass) -> Option<(Position, Compass)> {
+        let next = position.checked_next(direction)?;
+
+        let state = match board.case(next)? {
+            Case::Free() => (next, direction),
+            Case::OneWay(way) if way == direction => (next, direction),
+            Case::Teleporter(_, twin) => (twin, direction),
+            Case::Obstacle() | Case::OneWay(_) => (position, self.turn(direction)),
+            Case::DoubleWall() => (position, self.turn(self.turn(direction))),
+        };
+
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, Guard, StepOutcome};
+
+    #[test]
+    fn turns() {
+        let left = Rules { turn: Turn::Left, diagonal: false };
+        let reverse = Rules { turn: Turn::Reverse, diagonal: true };
+        let diagonal = Rules { turn: Turn::Right, diagonal: true };
+
+        assert_eq!(Rules::default().turn(Compass::Left), Compass::Up);
+        assert_eq!(left.turn(Compass::Up), Compass::Left);
+        assert_eq!(reverse.turn(Compass::UpRight), Compass::DownLeft);
+        assert_eq!(diagonal.turn(Compass::Up), Compass::UpRight);
+        assert_eq!(diagonal.turn(Compass::UpLeft), Compass::Up);
+    }
+
+    #[test]
+    fn tiles() {
+        // One-way tiles only let the guard through in their direction.
+        let (position, direction, grid) = parse_input("#.\nN.\n^.").unwrap();
+        let mut guard = Guard::new(grid, position, direction);
+        assert_eq!(guard.walk(), StepOutcome::Moved(Position { x: 0, y: 1 }));
+        assert_eq!(guard.walk(), StepOutcome::Turned(Compass::Right));
+
+        let (position, direction, grid) = parse_input("S.\n^.").unwrap();
+        let mut guard = Guard::new(grid, position, direction);
+        assert_eq!(guard.walk(), StepOutcome::Turned(Compass::Right));
+
+        // Teleporters send the guard onto their twin.
+        let (position, direction, grid) = parse_input("1..\n..1\n^..").unwrap();
+        let mut guard = Guard::new(grid, position, direction);
+        assert_eq!(guard.walk(), StepOutcome::Moved(Position { x: 0, y: 1 }));
+        assert_eq!(guard.walk(), StepOutcome::Moved(Position { x: 2, y: 1 }));
+        assert_eq!(guard.walk(), StepOutcome::Moved(Position { x: 2, y: 0 }));
+
+        // Double walls make the guard turn twice.
+        let (position, direction, grid) = parse_input("@\n^").unwrap();
+        let mut guard = Guard::new(grid, position, direction);
+        assert_eq!(guard.walk(), StepOutcome::Turned(Compass::Down));
+    }
+
+    #[test]
+    fn diagonal_patrol() {
+        let (position, direction, grid) = parse_input("#..\n...\n^..").unwrap();
+        let rules = Rules { turn: Turn::Right, diagonal: true };
+        let mut guard = Guard::with_rules(grid, position, direction, rules);
+
+        let steps: Vec<StepOutcome> = guard.steps().collect();
+        assert_eq!(
+            steps,
+            vec![
+                StepOutcome::Moved(Position { x: 0, y: 1 }),
+                StepOutcome::Turned(Compass::UpRight),
+                StepOutcome::Moved(Position { x: 1, y: 0 }),
+                StepOutcome::Exited,
+            ]
+        );
+    }
+}
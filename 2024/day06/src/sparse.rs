@@ -0,0 +1,255 @@
+//! A board storing only its obstacles and special tiles, for huge maps which
+//! are mostly empty. It is either bounded by a width and a height, or an
+//! infinite plane the guard only leaves at the edge of the `i32` range:
+//! patrols on it need a step limit, see `Guard::patrol_for`.
+//!
+//! Such boards are loaded from coordinate lists:
+//!
+//! ```text
+//! size 10 10
+//! guard 4 6 ^
+//! 4 0
+//! 9,1
+//! ```
+//!
+//! `size` is optional, without it the plane is infinite. Every other line is
+//! the position of an obstacle.
+
+use std::collections::HashMap;
+
+use common::error::parse_token;
+use common::ParseError;
+
+use crate::{Board, Case, Compass, Position};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SparseBoard {
+    bounds: Option<(usize, usize)>,
+    /// Every case which is not free.
+    cases: HashMap<Position, Case>,
+}
+
+impl SparseBoard {
+    pub fn bounded(width: usize, height: usize) -> SparseBoard {
+        SparseBoard { bounds: Some((width, height)), cases: HashMap::new() }
+    }
+
+    pub fn unbounded() -> SparseBoard {
+        SparseBoard { bounds: None, cases: HashMap::new() }
+    }
+
+    pub fn bounds(&self) -> Option<(usize, usize)> {
+        self.bounds
+    }
+
+    fn contains(&self, position: Position) -> bool {
+        match self.bounds {
+            Some((width, height)) => {
+                position.x >= 0 && position.y >= 0 && (position.x as usize) < width && (position.y as usize) < height
+            }
+            None => true,
+        }
+    }
+}
+
+impl Board for SparseBoard {
+    type History = HashMap<Position, Vec<Compass>>;
+
+    fn case(&self, position: Position) -> Option<Case> {
+        if !self.contains(position) {
+            return None;
+        }
+
+        Some(self.cases.get(&position).copied().unwrap_or(Case::Free()))
+    }
+
+    fn set_case(&mut self, position: Position, case: Case) {
+        if !self.contains(position) {
+            return;
+        }
+
+        match case {
+            Case::Free() => self.cases.remove(&position),
+            case => self.cases.insert(position, case),
+        };
+    }
+
+    /// The bounds, or the smallest map from the origin holding every obstacle
+    /// of an infinite plane.
+    fn size(&self) -> (usize, usize) {
+        if let Some(bounds) = self.bounds {
+            return bounds;
+        }
+
+        let last = |coordinate: fn(&Position) -> i32| self.cases.keys().map(coordinate).max().map_or(0, |last| (last as i64 + 1).max(0) as usize);
+
+        (last(|position| position.x), last(|position| position.y))
+    }
+
+    fn history(&self) -> Self::History {
+        HashMap::new()
+    }
+}
+
+pub fn parse_coordinates(input: &str) -> common::Result<(Position, Compass, SparseBoard)> {
+    let input = input.trim_end();
+    let mut board = SparseBoard::unbounded();
+    let mut guard = None;
+    let mut obstacles = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let tokens: Vec<&str> = line
+            .split(|char: char| char == ',' || char.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .collect();
+
+        match tokens.as_slice() {
+            [] => {}
+            ["size", width, height] => {
+                board.bounds = Some((parse_token(line_number, line, width)?, parse_token(line_number, line, height)?));
+            }
+            ["guard", x, y, direction] => {
+                let position = Position { x: parse_token(line_number, line, x)?, y: parse_token(line_number, line, y)? };
+                let direction = match *direction {
+                    "^" => Compass::Up,
+                    ">" => Compass::Right,
+                    "v" => Compass::Down,
+                    "<" => Compass::Left,
+                    other => {
+                        let message = format!("unexpected direction `{}`, expected one of `^`, `>`, `v` or `<`", other);
+                        return Err(ParseError::at(line_number, line, other, message));
+                    }
+                };
+
+                if guard.is_some() {
+                    return Err(ParseError::line(line_number, line, "a second guard is on the map, expected only one"));
+                }
+
+                guard = Some((position, direction, line_number, line));
+            }
+            [x, y] => {
+                let position = Position { x: parse_token(line_number, line, x)?, y: parse_token(line_number, line, y)? };
+                obstacles.push((position, line_number, line));
+            }
+            _ => {
+                return Err(ParseError::line(line_number, line, "expected `X Y`, `guard X Y DIRECTION` or `size WIDTH HEIGHT`"));
+            }
+        }
+    }
+
+    for (position, line_number, line) in obstacles {
+        if !board.contains(position) {
+            return Err(ParseError::line(line_number, line, "obstacle outside of the map"));
+        }
+
+        board.set_case(position, Case::Obstacle());
+    }
+
+    match guard {
+        Some((position, _, line_number, line)) if !board.contains(position) => {
+            Err(ParseError::line(line_number, line, "guard outside of the map"))
+        }
+        Some((position, _, line_number, line)) if board.case(position) != Some(Case::Free()) => {
+            Err(ParseError::line(line_number, line, "guard on an obstacle"))
+        }
+        Some((position, direction, _, _)) => Ok((position, direction, board)),
+        None => {
+            let line = input.lines().last().unwrap_or("");
+            let line_number = input.lines().count().max(1);
+            Err(ParseError::end_of_line(line_number, line, "no guard found in the list"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parallel, parse_input, Guard, Rules, SAMPLE};
+
+    const COORDINATES: &str = "size 10 10\n\
+                               guard 4 6 ^\n\
+                               4 0\n\
+                               9,1\n\
+                               2 3\n\
+                               7 4\n\
+                               1 6\n\
+                               8 7\n\
+                               0 8\n\
+                               6 9\n";
+
+    #[test]
+    fn same_as_grid() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let (sparse_position, sparse_direction, board) = parse_coordinates(COORDINATES).unwrap();
+
+        assert_eq!((sparse_position, sparse_direction), (position, direction));
+        assert_eq!(board.size(), (10, 10));
+
+        for (position, &case) in grid.iter() {
+            assert_eq!(board.case(position), Some(case), "{:?}", position);
+        }
+        assert_eq!(board.case(Position { x: 10, y: 0 }), None);
+
+        let mut guard = Guard::new(board.clone(), position, direction);
+        guard.patrol();
+        assert_eq!(guard.history.len(), 41);
+
+        assert_eq!(parallel::looping_obstacles(&board, position, direction, Rules::default(), 2).len(), 6);
+    }
+
+    #[test]
+    fn infinite_plane() {
+        let (position, direction, board) = parse_coordinates("guard 1000000 -5 >").unwrap();
+        let mut guard = Guard::new(board, position, direction);
+
+        assert!(!guard.patrol_for(100));
+        assert_eq!(guard.history.len(), 101);
+        assert_eq!(guard.position, Position { x: 1000100, y: -5 });
+
+        // Boxed in, the guard is trapped even without bounds.
+        let (position, direction, board) = parse_coordinates("guard 0 0 ^\n0 -1\n1 0\n0 1\n-1 0").unwrap();
+        let mut guard = Guard::new(board, position, direction);
+
+        assert!(guard.patrol_for(100));
+        assert!(guard.looping);
+    }
+
+    #[test]
+    fn edge_of_the_plane() {
+        let (position, direction, board) = parse_coordinates("guard 2147483640 0 >").unwrap();
+        let mut guard = Guard::new(board, position, direction);
+
+        assert!(guard.patrol_for(100));
+        assert!(!guard.walking && !guard.looping);
+        assert_eq!(guard.position, Position { x: i32::MAX, y: 0 });
+        assert_eq!(guard.history.len(), 8);
+
+        let (_, _, board) = parse_coordinates("guard 0 0 ^\n2147483647 -2147483648").unwrap();
+        assert_eq!(board.size(), (2147483648, 0));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = parse_coordinates("guard 1 1 ^\n3 x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = parse_coordinates("size 5 5\nguard 1 1 ^\n3 5").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (3, "obstacle outside of the map"));
+
+        let error = parse_coordinates("guard 1 1 up").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
+
+        let error = parse_coordinates("1 2 3").unwrap_err();
+        assert_eq!(error.line, 1);
+
+        let error = parse_coordinates("1 2").unwrap_err();
+        assert_eq!(error.message, "no guard found in the list");
+
+        let error = parse_coordinates("guard 0 0 ^\nguard 5 5 v\nsize 10 10").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (2, "a second guard is on the map, expected only one"));
+
+        let error = parse_coordinates("guard 0 0 ^\n0 0").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (1, "guard on an obstacle"));
+    }
+}
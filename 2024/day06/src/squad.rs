@@ -0,0 +1,183 @@
+//! Several guards patrolling the same map, each one taking a step on every
+//! tick. Guards don't block each other, they only report when they collide.
+//! Each guard keeps its own history to find its own loop, while a shared
+//! history records every case covered by the squad.
+
+use crate::{Board, Compass, Guard, History, Position, Rules, StepOutcome};
+
+/// Two guards standing on the same case at the end of a tick, or swapping
+/// cases during it. In that case `position` is where the first one ended.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Collision {
+    pub tick: usize,
+    pub guards: (usize, usize),
+    pub position: Position,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Report {
+    pub ticks: usize,
+    pub collisions: Vec<Collision>,
+    /// Number of distinct cases visited by each guard.
+    pub visited: Vec<usize>,
+    /// Whether each guard ended up looping instead of leaving the map.
+    pub looping: Vec<bool>,
+    /// Every case visited by at least one guard, sorted.
+    pub covered: Vec<Position>,
+}
+
+pub struct Squad<B: Board> {
+    pub guards: Vec<Guard<B>>,
+    pub tick: usize,
+    covered: B::History,
+}
+
+impl<B: Board + Clone> Squad<B> {
+    pub fn new(board: &B, guards: &[(Position, Compass)], rules: Rules) -> Squad<B> {
+        let mut covered = board.history();
+
+        let guards = guards
+            .iter()
+            .map(|&(position, direction)| {
+                covered.visit(position, direction);
+                Guard::with_rules(board.clone(), position, direction, rules)
+            })
+            .collect();
+
+        Squad { guards, tick: 0, covered }
+    }
+}
+
+impl<B: Board> Squad<B> {
+    /// Whether every guard has left the map or is looping.
+    pub fn is_over(&self) -> bool {
+        self.guards.iter().all(|guard| !guard.walking || guard.looping)
+    }
+
+    /// Move every guard still on the map by one step, returning the
+    /// collisions of this tick.
+    pub fn tick(&mut self) -> Vec<Collision> {
+        let before: Vec<Position> = self.guards.iter().map(|guard| guard.position).collect();
+
+        for guard in &mut self.guards {
+            // A looping guard is done with its history, but keeps walking
+            // around its loop, where it may still meet the others.
+            let outcome = if guard.looping { StepOutcome::LoopDetected } else { guard.walk() };
+
+            match outcome {
+                StepOutcome::LoopDetected => {
+                    if let Some((position, direction)) = guard.rules.step(&guard.grid, guard.position, guard.direction) {
+                        guard.position = position;
+                        guard.direction = direction;
+                    }
+                }
+                StepOutcome::Moved(_) | StepOutcome::Turned(_) => {
+                    self.covered.visit(guard.position, guard.direction);
+                }
+                StepOutcome::Exited => {}
+            }
+        }
+
+        self.tick += 1;
+        self.collisions(&before)
+    }
+
+    /// Run the squad until every guard has left the map or is looping.
+    pub fn run(&mut self) -> Report {
+        let mut collisions = Vec::new();
+
+        while !self.is_over() {
+            collisions.extend(self.tick());
+        }
+
+        let mut covered = self.covered.positions();
+        covered.sort();
+
+        Report {
+            ticks: self.tick,
+            collisions,
+            visited: self.guards.iter().map(|guard| guard.history.len()).collect(),
+            looping: self.guards.iter().map(|guard| guard.looping).collect(),
+            covered,
+        }
+    }
+
+    fn collisions(&self, before: &[Position]) -> Vec<Collision> {
+        let mut collisions = Vec::new();
+
+        for (first, guard) in self.guards.iter().enumerate() {
+            for (second, other) in self.guards.iter().enumerate().skip(first + 1) {
+                if !guard.walking || !other.walking {
+                    continue;
+                }
+
+                let met = guard.position == other.position;
+                let crossed = guard.position == before[second]
+                    && other.position == before[first]
+                    && before[first] != before[second];
+
+                if met || crossed {
+                    collisions.push(Collision { tick: self.tick, guards: (first, second), position: guard.position });
+                }
+            }
+        }
+
+        collisions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_guards, parse_input, DenseBoard, SAMPLE};
+
+    fn run(input: &str) -> Report {
+        let (guards, grid) = parse_guards(input).unwrap();
+        Squad::new(&DenseBoard::from(&grid), &guards, Rules::default()).run()
+    }
+
+    #[test]
+    fn single_guard() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let report = Squad::new(&grid, &[(position, direction)], Rules::default()).run();
+
+        assert_eq!(report.visited, vec![41]);
+        assert_eq!(report.covered.len(), 41);
+        assert_eq!(report.looping, vec![false]);
+        assert!(report.collisions.is_empty());
+    }
+
+    #[test]
+    fn collisions() {
+        let report = run(">.<");
+        assert_eq!(report.collisions, vec![Collision { tick: 1, guards: (0, 1), position: Position { x: 1, y: 0 } }]);
+
+        // Guards swapping cases cross each other.
+        let report = run(">..<");
+        assert_eq!(report.ticks, 4);
+        assert_eq!(report.collisions, vec![Collision { tick: 2, guards: (0, 1), position: Position { x: 2, y: 0 } }]);
+        assert_eq!(report.visited, vec![4, 4]);
+        assert_eq!(report.covered.len(), 4);
+    }
+
+    #[test]
+    fn looping_and_leaving() {
+        let report = run("###..\n#^#..\n###.>");
+
+        assert_eq!(report.ticks, 4);
+        assert_eq!(report.looping, vec![true, false]);
+        assert_eq!(report.visited, vec![1, 1]);
+        assert_eq!(report.covered, vec![Position { x: 1, y: 1 }, Position { x: 4, y: 2 }]);
+        assert!(report.collisions.is_empty());
+    }
+
+    #[test]
+    fn shared_coverage() {
+        // Both guards walk the middle column, it is only covered once.
+        let report = run(".v.\n...\n.^.");
+
+        assert_eq!(report.visited, vec![3, 3]);
+        assert_eq!(report.covered, vec![Position { x: 1, y: 0 }, Position { x: 1, y: 1 }, Position { x: 1, y: 2 }]);
+        assert_eq!(report.collisions, vec![Collision { tick: 1, guards: (0, 1), position: Position { x: 1, y: 1 } }]);
+    }
+}
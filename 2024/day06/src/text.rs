@@ -0,0 +1,156 @@
+//! Write a map back as puzzle text, the inverse of `parse_input`. The visited
+//! cases can be marked with `X` and an inserted obstacle with `O`, like in the
+//! puzzle examples; the parser reads them back as free cases and obstacles.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::render::glyph;
+use crate::{Case, Compass, Grid, Position};
+
+pub struct MapText<'a> {
+    grid: &'a Grid<Case>,
+    position: Position,
+    direction: Compass,
+    visited: HashSet<Position>,
+    obstacle: Option<Position>,
+}
+
+impl<'a> MapText<'a> {
+    pub fn new(grid: &'a Grid<Case>, position: Position, direction: Compass) -> MapText<'a> {
+        MapText { grid, position, direction, visited: HashSet::new(), obstacle: None }
+    }
+
+    /// Mark the free `visited` cases with `X`.
+    pub fn with_visited(mut self, visited: impl IntoIterator<Item = Position>) -> MapText<'a> {
+        self.visited.extend(visited);
+        self
+    }
+
+    /// Mark an inserted obstacle with `O`.
+    pub fn with_obstacle(mut self, obstacle: Position) -> MapText<'a> {
+        self.obstacle = Some(obstacle);
+        self
+    }
+
+    fn symbol(&self, position: Position, case: Case) -> char {
+        if position == self.position {
+            glyph(self.direction)
+        } else if Some(position) == self.obstacle {
+            'O'
+        } else if case == Case::Free() && self.visited.contains(&position) {
+            'X'
+        } else {
+            case.symbol()
+        }
+    }
+}
+
+impl fmt::Display for MapText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (position, &case) in self.grid.iter() {
+            if position.x == 0 && position.y > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}", self.symbol(position, case))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, Guard, History, SAMPLE};
+
+    /// A xorshift generator, enough to draw random maps.
+    struct Random(u64);
+
+    impl Random {
+        fn below(&mut self, bound: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % bound as u64) as usize
+        }
+    }
+
+    /// A rectangular map with one guard, some special tiles, and every
+    /// teleporter digit used twice.
+    fn random_map(random: &mut Random) -> String {
+        let (width, height) = (1 + random.below(12), 1 + random.below(12));
+        let mut cells: Vec<char> = (0..width * height).map(|_| ['.', '.', '.', '#', '@', 'N', 'E', 'S', 'W'][random.below(9)]).collect();
+
+        let mut free: Vec<usize> = (0..cells.len()).collect();
+        let mut take = |random: &mut Random| free.swap_remove(random.below(free.len()));
+
+        cells[take(random)] = ['^', '>', 'v', '<'][random.below(4)];
+
+        for digit in ['1', '2', '3'].iter().take((cells.len() - 1) / 2) {
+            if random.below(2) == 0 {
+                cells[take(random)] = *digit;
+                cells[take(random)] = *digit;
+            }
+        }
+
+        cells
+            .chunks(width)
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn round_trip() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+
+        assert_eq!(MapText::new(&grid, position, direction).to_string(), SAMPLE);
+    }
+
+    #[test]
+    fn round_trip_random_maps() {
+        let mut random = Random(0x2024_0006);
+
+        for _ in 0..500 {
+            let map = random_map(&mut random);
+            let (position, direction, grid) = parse_input(&map).unwrap();
+            let text = MapText::new(&grid, position, direction).to_string();
+
+            assert_eq!(text, map);
+            assert_eq!(parse_input(&text).unwrap(), (position, direction, grid));
+        }
+    }
+
+    #[test]
+    fn overlays() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let mut guard = Guard::new(grid.clone(), position, direction);
+        guard.patrol();
+
+        let obstacle = Position { x: 3, y: 6 };
+        let text = MapText::new(&grid, position, direction)
+            .with_visited(guard.history.positions())
+            .with_obstacle(obstacle)
+            .to_string();
+
+        assert_eq!(
+            text,
+            "....#.....\n\
+             ....XXXXX#\n\
+             ....X...X.\n\
+             ..#.X...X.\n\
+             ..XXXXX#X.\n\
+             ..X.X.X.X.\n\
+             .#XO^XXXX.\n\
+             .XXXXXXX#.\n\
+             #XXXXXXX..\n\
+             ......#X.."
+        );
+
+        let mut expected = grid.clone();
+        expected[obstacle] = Case::Obstacle();
+        assert_eq!(parse_input(&text).unwrap(), (position, direction, expected));
+    }
+}
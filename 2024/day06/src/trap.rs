@@ -0,0 +1,157 @@
+//! The fewest obstacles to add so the guard never leaves the map.
+//!
+//! The search deepens one obstacle at a time, so the first trap found is a
+//! smallest one. Obstacles are only tried on the cases of the path the guard
+//! currently escapes by: a set of obstacles missing that path lets it escape
+//! the same way. Sets already tried in another order are skipped.
+
+use std::collections::HashSet;
+
+use crate::{Board, Case, Compass, Guard, History, Position, Rules};
+
+/// One smallest set of obstacles trapping the guard, sorted, or `None` when
+/// more than `max` obstacles are needed.
+pub fn min_obstacles<B: Board + Clone>(board: &B, position: Position, direction: Compass, rules: Rules, max: usize) -> Option<Vec<Position>> {
+    let mut search = Search { position, direction, rules, board: board.clone(), placed: Vec::new(), tried: HashSet::new() };
+
+    for depth in 0..=max {
+        search.tried.clear();
+
+        if search.trap(depth) {
+            let mut obstacles = search.placed;
+            obstacles.sort();
+            return Some(obstacles);
+        }
+    }
+
+    None
+}
+
+struct Search<B> {
+    position: Position,
+    direction: Compass,
+    rules: Rules,
+    board: B,
+    placed: Vec<Position>,
+    tried: HashSet<Vec<Position>>,
+}
+
+impl<B: Board + Clone> Search<B> {
+    /// Whether at most `depth` more obstacles trap the guard, leaving them in
+    /// `placed` when they do.
+    fn trap(&mut self, depth: usize) -> bool {
+        let mut guard = Guard::with_rules(self.board.clone(), self.position, self.direction, self.rules);
+        guard.patrol();
+
+        if guard.looping {
+            return true;
+        }
+
+        if depth == 0 {
+            return false;
+        }
+
+        let mut candidates: Vec<Position> = guard.history
+            .positions()
+            .into_iter()
+            .filter(|&candidate| candidate != self.position && self.board.case(candidate) == Some(Case::Free()))
+            .collect();
+        candidates.sort();
+
+        for candidate in candidates {
+            self.placed.push(candidate);
+
+            let mut set = self.placed.clone();
+            set.sort();
+
+            if self.tried.insert(set) {
+                self.board.set_case(candidate, Case::Obstacle());
+
+                if self.trap(depth - 1) {
+                    return true;
+                }
+
+                self.board.set_case(candidate, Case::Free());
+            }
+
+            self.placed.pop();
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{looping_obstacles, parse_input, DenseBoard, SAMPLE};
+
+    fn loops(board: &DenseBoard, position: Position, direction: Compass, obstacles: &[Position]) -> bool {
+        let mut board = board.clone();
+        for &obstacle in obstacles {
+            board.set_case(obstacle, Case::Obstacle());
+        }
+
+        let mut guard = Guard::new(board, position, direction);
+        guard.patrol();
+        guard.looping
+    }
+
+    /// Whether any set of `count` free cases traps the guard.
+    fn any_trap(board: &DenseBoard, position: Position, direction: Compass, count: usize, from: usize, set: &mut Vec<Position>) -> bool {
+        if set.len() == count {
+            return loops(board, position, direction, set);
+        }
+
+        let cells = board.width() * board.height();
+
+        (from..cells).any(|index| {
+            let cell = Position { x: (index % board.width()) as i32, y: (index / board.width()) as i32 };
+            if cell == position || board.case(cell) != Some(Case::Free()) {
+                return false;
+            }
+
+            set.push(cell);
+            let found = any_trap(board, position, direction, count, index + 1, set);
+            set.pop();
+            found
+        })
+    }
+
+    #[test]
+    fn sample() {
+        let (position, direction, grid) = parse_input(SAMPLE).unwrap();
+        let obstacles = min_obstacles(&grid, position, direction, Rules::default(), 3).unwrap();
+
+        assert_eq!(obstacles.len(), 1);
+        assert!(looping_obstacles(&grid, position, direction).contains(&obstacles[0]));
+    }
+
+    #[test]
+    fn already_trapped() {
+        let (position, direction, grid) = parse_input(".#..\n...#\n#^..\n..#.").unwrap();
+
+        assert_eq!(min_obstacles(&grid, position, direction, Rules::default(), 2), Some(vec![]));
+    }
+
+    #[test]
+    fn not_enough_obstacles() {
+        let (position, direction, grid) = parse_input("...\n.^.\n...").unwrap();
+
+        assert_eq!(min_obstacles(&grid, position, direction, Rules::default(), 2), None);
+    }
+
+    #[test]
+    fn smallest_against_brute_force() {
+        for map in ["....\n.^..\n....\n....", "#...\n..#.\n.^..\n....", "..#.\n....\n^...\n...#"] {
+            let (position, direction, grid) = parse_input(map).unwrap();
+            let board = DenseBoard::from(&grid);
+            let obstacles = min_obstacles(&board, position, direction, Rules::default(), 4).unwrap();
+
+            assert!(loops(&board, position, direction, &obstacles), "{}", map);
+            for count in 0..obstacles.len() {
+                assert!(!any_trap(&board, position, direction, count, 0, &mut Vec::new()), "{}", map);
+            }
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! A rectangular grid of cells parsed from text, with the positions and
+//! directions to walk on it.
+
+use std::ops::{Index, IndexMut};
+
+use common::ParseError;
+
+pub use common::geometry::{Compass, Direction, Vec2};
+
+/// A case of the grid.
+pub type Position = Vec2;
+
+/// What to do with lines shorter than the longest one when parsing.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum Ragged {
+    /// Fail on the first line whose length differs from the first line.
+    #[default]
+    Reject,
+    /// Fill the end of the short lines with this character.
+    Pad(char),
+}
+
+/// Cells stored row after row.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Grid<T> {
+        Grid { width, height, cells: vec![cell; width * height] }
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` columns, the rows following each other in `cells`.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Grid<T> {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "the cells don't fill the last row");
+
+        Grid { width, height, cells }
+    }
+
+    /// Parse one cell per character of each line with `cell`, which explains
+    /// why a character is invalid. Every line must have the same length.
+    pub fn parse(input: &str, cell: impl FnMut(Position, char) -> Result<T, String>) -> common::Result<Grid<T>> {
+        Grid::parse_with(input, Ragged::Reject, cell)
+    }
+
+    /// Same as `parse`, with lines of different lengths handled by `ragged`.
+    /// Both `\n` and `\r\n` end a line, and trailing empty lines are
+    /// ignored.
+    pub fn parse_with(input: &str, ragged: Ragged, mut cell: impl FnMut(Position, char) -> Result<T, String>) -> common::Result<Grid<T>> {
+        let input = input.trim_end_matches(['\n', '\r']);
+        let width = match ragged {
+            Ragged::Reject => input.lines().next().map_or(0, |line| line.chars().count()),
+            Ragged::Pad(_) => input.lines().map(|line| line.chars().count()).max().unwrap_or(0),
+        };
+        let mut cells = Vec::new();
+
+        for (row, line) in input.lines().enumerate() {
+            let length = line.chars().count();
+
+            if ragged == Ragged::Reject && length != width {
+                let message = format!("expected {} cases on this line, found {}", width, length);
+                return Err(ParseError::end_of_line(row + 1, line, message));
+            }
+
+            for (col, (offset, char)) in line.char_indices().enumerate() {
+                let position = Position { x: col as i32, y: row as i32 };
+
+                match cell(position, char) {
+                    Ok(cell) => cells.push(cell),
+                    Err(message) => return Err(ParseError::at(row + 1, line, &line[offset..offset + char.len_utf8()], message)),
+                }
+            }
+
+            if let Ragged::Pad(padding) = ragged {
+                for col in length..width {
+                    let position = Position { x: col as i32, y: row as i32 };
+                    cells.push(cell(position, padding).map_err(|message| ParseError::end_of_line(row + 1, line, message))?);
+                }
+            }
+        }
+
+        Ok(Grid::from_cells(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.index(position).is_some()
+    }
+
+    /// The cell at `position`, `None` when it is outside of the grid.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index(position).map(|index| &mut self.cells[index])
+    }
+
+    /// `position` brought back on the grid as if it was a torus: the first
+    /// column follows the last one, and the first row the last one.
+    pub fn wrap(&self, position: Position) -> Position {
+        Position { x: position.x.rem_euclid(self.width as i32), y: position.y.rem_euclid(self.height as i32) }
+    }
+
+    /// Every position, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| Position { x: (index % width) as i32, y: (index / width) as i32 })
+    }
+
+    /// Every cell with its position, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The cells above, right, below and left of `position`, when they exist.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(position, Direction::ALL.map(Compass::from))
+    }
+
+    /// The cells around `position`, diagonals included, when they exist.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(position, Compass::ALL)
+    }
+
+    /// The cells from `position` included, walking in `direction` until the
+    /// edge of the grid.
+    pub fn ray(&self, position: Position, direction: impl Into<Compass>) -> Ray<'_, T> {
+        Ray { grid: self, position, direction: direction.into() }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(if x < self.width { self.height } else { 0 })
+    }
+
+    pub fn map<U>(&self, cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(cell).collect() }
+    }
+
+    fn neighbours<const N: usize>(&self, position: Position, directions: [Compass; N]) -> impl Iterator<Item = (Position, &T)> {
+        directions.into_iter().filter_map(move |direction| {
+            let neighbour = position.next(direction);
+            self.get(neighbour).map(|cell| (neighbour, cell))
+        })
+    }
+
+    fn index(&self, position: Position) -> Option<usize> {
+        if position.x < 0 || position.y < 0 {
+            return None;
+        }
+
+        let (x, y) = (position.x as usize, position.y as usize);
+
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(x + y * self.width)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| panic!("{:?} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position).unwrap_or_else(|| panic!("{:?} is outside of the grid", position))
+    }
+}
+
+/// Iterator over the cells of a line of the grid, see `Grid::ray`.
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    position: Position,
+    direction: Compass,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<(Position, &'a T)> {
+        let position = self.position;
+        let cell = self.grid.get(position)?;
+        self.position = position.next(self.direction);
+
+        Some((position, cell))
+    }
+}
+
+/// An error on the character at `position` of the text `input` was parsed
+/// from.
+pub fn error_at(input: &str, position: Position, message: impl Into<String>) -> ParseError {
+    let line = input.lines().nth(position.y as usize).unwrap_or("");
+    let line_number = position.y as usize + 1;
+
+    match line.char_indices().nth(position.x as usize) {
+        Some((offset, char)) => ParseError::at(line_number, line, &line[offset..offset + char.len_utf8()], message),
+        None => ParseError::end_of_line(line_number, line, message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\r\n", |_, char| Ok(char)).unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Position { x: 2, y: 1 }), Some(&'f'));
+        assert_eq!(grid.get(Position { x: 3, y: 1 }), None);
+        assert_eq!(grid.get(Position { x: 0, y: -1 }), None);
+        assert_eq!(grid[Position { x: 1, y: 0 }], 'b');
+        assert_eq!(Grid::parse("", |_, char| Ok(char)).unwrap(), Grid::from_cells(0, vec![]));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Grid::parse("ab\nabc", |_, char| Ok(char)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.message, "expected 2 cases on this line, found 3");
+
+        let error = Grid::parse("ab\naé", |_, char| if char == 'é' { Err("no accents".to_string()) } else { Ok(char) })
+            .unwrap_err();
+        assert_eq!((error.line, error.column, error.width), (2, 2, 1));
+        assert_eq!(error.message, "no accents");
+
+        let error = error_at("ab\naé", Position { x: 1, y: 1 }, "here");
+        assert_eq!((error.line, error.column, error.width), (2, 2, 1));
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let input = "ab\r\nabc\r\na\r\n\r\n";
+
+        let error = Grid::parse_with(input, Ragged::Reject, |_, char| Ok(char)).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, "abc"));
+
+        let grid = Grid::parse_with(input, Ragged::Pad('.'), |_, char| Ok(char)).unwrap();
+        assert_eq!(grid, Grid::parse("ab.\nabc\na..", |_, char| Ok(char)).unwrap());
+
+        let error = Grid::parse_with("ab\na", Ragged::Pad('.'), |_, char| if char == '.' { Err("no dots".to_string()) } else { Ok(char) })
+            .unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (2, 2, "no dots"));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        let corner = Position { x: 0, y: 0 };
+
+        let cells: Vec<char> = grid.neighbours4(corner).map(|(_, &cell)| cell).collect();
+        assert_eq!(cells, vec!['b', 'd']);
+
+        let cells: Vec<char> = grid.neighbours8(Position { x: 1, y: 1 }).map(|(_, &cell)| cell).collect();
+        assert_eq!(cells, vec!['b', 'c', 'f', 'd', 'a']);
+    }
+
+    #[test]
+    fn rays() {
+        let grid = grid();
+
+        let cells: String = grid.ray(Position { x: 0, y: 0 }, Direction::Right).map(|(_, cell)| cell).collect();
+        assert_eq!(cells, "abc");
+
+        let cells: String = grid.ray(Position { x: 2, y: 1 }, Compass::UpLeft).map(|(_, cell)| cell).collect();
+        assert_eq!(cells, "fb");
+
+        assert_eq!(grid.ray(Position { x: 5, y: 0 }, Direction::Left).count(), 0);
+    }
+
+    #[test]
+    fn views() {
+        let grid = grid();
+
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.positions().last(), Some(Position { x: 2, y: 1 }));
+        assert_eq!(grid.wrap(Position { x: -1, y: 5 }), Position { x: 2, y: 1 });
+        assert_eq!(grid.map(|cell| cell.is_ascii_lowercase()), Grid::new(3, 2, true));
+    }
+}